@@ -1,6 +1,9 @@
+#![allow(dead_code)]
 use std::fs::File;
-use std::io::{Write, Read};
-use std::collections::BTreeMap;
+use std::io::{self, Write, Read};
+use std::collections::{BTreeMap, BinaryHeap};
+use std::cmp::Ordering;
+use std::fmt;
 
 #[derive(Debug, Clone)]
 struct HuffmanNode {
@@ -21,16 +24,72 @@ impl HuffmanNode {
     }
 }
 
+// Reversed on frequency so BinaryHeap pops the least frequent node first
+impl Ord for HuffmanNode {
+    fn cmp(&self, other: &Self) -> Ordering {
+        other.frequency.cmp(&self.frequency)
+    }
+}
+
+impl PartialOrd for HuffmanNode {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl PartialEq for HuffmanNode {
+    fn eq(&self, other: &Self) -> bool {
+        self.frequency == other.frequency
+    }
+}
+
+impl Eq for HuffmanNode {}
+
+#[derive(Debug, PartialEq)]
+enum HuffmanError {
+    Truncated,
+    InvalidCode,
+    KraftViolation,
+    CodeTooLong(u8),
+    TreeTooDeep,
+    InputTooLarge(usize),
+    MissingCode(u8),
+    NotPrefixFree,
+}
+
+impl fmt::Display for HuffmanError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            HuffmanError::Truncated => write!(f, "compressed data is truncated"),
+            HuffmanError::InvalidCode => write!(f, "bit sequence does not match any code"),
+            HuffmanError::KraftViolation => write!(f, "code lengths violate Kraft's inequality"),
+            HuffmanError::CodeTooLong(length) => write!(f, "code length {} exceeds {} bits", length, MAX_CODE_LENGTH),
+            HuffmanError::TreeTooDeep => write!(f, "serialized tree is deeper than {} levels", MAX_TREE_DEPTH),
+            HuffmanError::InputTooLarge(len) => write!(f, "input of {} bytes does not fit the u32 length header", len),
+            HuffmanError::MissingCode(byte) => write!(f, "no code for byte {:#04x}", byte),
+            HuffmanError::NotPrefixFree => write!(f, "code table is not prefix-free"),
+        }
+    }
+}
+
+impl std::error::Error for HuffmanError {}
+
+// Lets file-level functions report corrupt input through io::Result
+impl From<HuffmanError> for io::Error {
+    fn from(error: HuffmanError) -> Self {
+        io::Error::new(io::ErrorKind::InvalidData, error)
+    }
+}
+
 fn build_huffman_tree(frequencies: &[(u8, usize)]) -> Option<Box<HuffmanNode>> {
-    let mut nodes: Vec<Box<HuffmanNode>> = frequencies
+    let mut heap: BinaryHeap<Box<HuffmanNode>> = frequencies
         .iter()
         .map(|&(symbol, frequency)| Box::new(HuffmanNode::new(frequency, Some(symbol))))
         .collect();
 
-    while nodes.len() > 1 {
-        nodes.sort_by_key(|n| n.frequency);
-        let left = nodes.remove(0);
-        let right = nodes.remove(0);
+    while heap.len() > 1 {
+        let left = heap.pop().unwrap();
+        let right = heap.pop().unwrap();
         let merged_frequency = left.frequency + right.frequency;
         let merged_node = Box::new(HuffmanNode {
             frequency: merged_frequency,
@@ -38,15 +97,17 @@ fn build_huffman_tree(frequencies: &[(u8, usize)]) -> Option<Box<HuffmanNode>> {
             left: Some(left),
             right: Some(right),
         });
-        nodes.push(merged_node);
+        heap.push(merged_node);
     }
-    nodes.pop()
+    heap.pop()
 }
 
 fn generate_codes(node: &Option<Box<HuffmanNode>>, prefix: String, codes: &mut BTreeMap<u8, String>) {
     if let Some(n) = node {
         if let Some(symbol) = n.symbol {
-            codes.insert(symbol, prefix);
+            // A lone leaf at the root still needs one bit per symbol
+            let code = if prefix.is_empty() { String::from("0") } else { prefix };
+            codes.insert(symbol, code);
         } else {
             generate_codes(&n.left, format!("{}0", prefix), codes);
             generate_codes(&n.right, format!("{}1", prefix), codes);
@@ -62,6 +123,41 @@ fn calculate_frequencies(data: &[u8]) -> Vec<(u8, usize)> {
     frequencies.into_iter().collect()
 }
 
+fn is_prefix_free(codes: &BTreeMap<u8, String>) -> bool {
+    let mut sorted: Vec<&String> = codes.values().collect();
+    sorted.sort();
+    sorted.windows(2).all(|w| !w[1].starts_with(w[0].as_str()))
+}
+
+fn shannon_entropy(data: &[u8]) -> f64 {
+    let total = data.len() as f64;
+    calculate_frequencies(data)
+        .iter()
+        .map(|&(_, frequency)| {
+            let p = frequency as f64 / total;
+            -p * p.log2()
+        })
+        .sum()
+}
+
+// actual_bits / entropy_bits - 1.0, 0.0 when the input carries no information
+fn optimality_gap(data: &[u8]) -> f64 {
+    let frequencies = calculate_frequencies(data);
+    let huffman_tree = build_huffman_tree(&frequencies);
+    let mut codes = BTreeMap::new();
+    generate_codes(&huffman_tree, String::new(), &mut codes);
+
+    let actual_bits: usize = frequencies
+        .iter()
+        .map(|(symbol, frequency)| frequency * codes[symbol].len())
+        .sum();
+    let entropy_bits = shannon_entropy(data) * data.len() as f64;
+    if entropy_bits == 0.0 {
+        return 0.0;
+    }
+    actual_bits as f64 / entropy_bits - 1.0
+}
+
 fn serialize_tree(node: &Option<Box<HuffmanNode>>, output: &mut Vec<u8>) {
     if let Some(n) = node {
         if let Some(symbol) = n.symbol {
@@ -77,6 +173,24 @@ fn serialize_tree(node: &Option<Box<HuffmanNode>>, output: &mut Vec<u8>) {
 
 fn encode_data(data: &[u8], codes: &BTreeMap<u8, String>) -> Vec<u8> {
     let bit_string: String = data.iter().map(|&b| codes.get(&b).unwrap().clone()).collect();
+    pack_bits(&bit_string)
+}
+
+// Same output as encode_data, but rejects tables that are not prefix-free or lack a code
+// for one of the input bytes instead of panicking
+fn encode_data_checked(data: &[u8], codes: &BTreeMap<u8, String>) -> Result<Vec<u8>, HuffmanError> {
+    if !is_prefix_free(codes) {
+        return Err(HuffmanError::NotPrefixFree);
+    }
+    let mut bit_string = String::new();
+    for &byte in data {
+        bit_string.push_str(codes.get(&byte).ok_or(HuffmanError::MissingCode(byte))?);
+    }
+    Ok(pack_bits(&bit_string))
+}
+
+// Packs a string of '0'/'1' MSB first, zero-padding the last byte
+fn pack_bits(bit_string: &str) -> Vec<u8> {
     let mut compressed_data = Vec::new();
     let mut byte = 0u8;
     let mut count = 0;
@@ -97,95 +211,688 @@ fn encode_data(data: &[u8], codes: &BTreeMap<u8, String>) -> Vec<u8> {
     compressed_data
 }
 
-fn write_binary_file(filename: &str, tree_data: &[u8], data: &[u8]) {
-    let mut file = File::create(filename).expect("Unable to create file");
-    file.write_all(tree_data).expect("Unable to write tree data");
-    file.write_all(data).expect("Unable to write compressed data");
+// Reads packed bits MSB first, stopping after `len` bits so the padding of the last
+// byte is never mistaken for data
+struct BitReader<'a> {
+    data: &'a [u8],
+    pos: usize,
+    len: usize,
+}
+
+impl<'a> BitReader<'a> {
+    fn new(data: &'a [u8]) -> Self {
+        BitReader { data, pos: 0, len: data.len() * 8 }
+    }
+
+    fn with_len(data: &'a [u8], len: usize) -> Self {
+        BitReader { data, pos: 0, len: len.min(data.len() * 8) }
+    }
+
+    fn read_bit(&mut self) -> Option<u8> {
+        if self.pos >= self.len {
+            return None;
+        }
+        let bit = (self.data[self.pos / 8] >> (7 - self.pos % 8)) & 1;
+        self.pos += 1;
+        Some(bit)
+    }
+
+    fn remaining(&self) -> usize {
+        self.len - self.pos
+    }
 }
 
-fn read_binary_file(filename: &str) -> Vec<u8> {
-    let mut file = File::open(filename).expect("Unable to open file");
+// Next symbol and the number of bits its code took. None at the end of the stream, on a
+// code cut short by the end of the stream, or on a path the tree doesn't have.
+fn decode_one(bits: &mut BitReader, root: &Option<Box<HuffmanNode>>) -> Option<(u8, usize)> {
+    let mut node = root.as_deref()?;
+    if let Some(symbol) = node.symbol {
+        // Single-symbol alphabet: every bit stands for the one symbol
+        bits.read_bit()?;
+        return Some((symbol, 1));
+    }
+    let mut consumed = 0;
+    loop {
+        let bit = bits.read_bit()?;
+        consumed += 1;
+        let child = if bit == 1 { &node.right } else { &node.left };
+        node = child.as_deref()?;
+        if let Some(symbol) = node.symbol {
+            return Some((symbol, consumed));
+        }
+    }
+}
+
+fn write_binary_file(filename: &str, tree_data: &[u8], data: &[u8]) -> io::Result<()> {
+    let mut file = File::create(filename)?;
+    file.write_all(tree_data)?;
+    file.write_all(data)
+}
+
+fn read_binary_file(filename: &str) -> io::Result<Vec<u8>> {
+    let mut file = File::open(filename)?;
     let mut contents = Vec::new();
-    file.read_to_end(&mut contents).expect("Unable to read data");
-    contents
+    file.read_to_end(&mut contents)?;
+    Ok(contents)
 }
 
-fn deserialize_tree(data: &mut &[u8]) -> Option<Box<HuffmanNode>> {
-    if data.is_empty() {
-        return None;
+// A tree over byte symbols has at most 256 leaves, so no valid tree goes deeper than this
+const MAX_TREE_DEPTH: usize = 255;
+
+// Truncated when the data ends before the tree is complete, TreeTooDeep when it nests
+// past MAX_TREE_DEPTH (which also keeps hostile input from overflowing the stack)
+fn deserialize_tree(data: &mut &[u8]) -> Result<Box<HuffmanNode>, HuffmanError> {
+    fn read_node(data: &mut &[u8], depth: usize) -> Result<Box<HuffmanNode>, HuffmanError> {
+        if depth > MAX_TREE_DEPTH {
+            return Err(HuffmanError::TreeTooDeep);
+        }
+        let (&is_leaf, rest) = data.split_first().ok_or(HuffmanError::Truncated)?;
+        *data = rest;
+
+        if is_leaf == 1 {
+            let (&symbol, rest) = data.split_first().ok_or(HuffmanError::Truncated)?;
+            *data = rest;
+            Ok(Box::new(HuffmanNode::new(0, Some(symbol))))
+        } else {
+            let left = read_node(data, depth + 1)?;
+            let right = read_node(data, depth + 1)?;
+            Ok(Box::new(HuffmanNode {
+                frequency: 0,
+                symbol: None,
+                left: Some(left),
+                right: Some(right),
+            }))
+        }
     }
 
-    let is_leaf = data[0];
-    *data = &data[1..];
+    read_node(data, 0)
+}
 
-    if is_leaf == 1 {
-        let symbol = data[0];
-        *data = &data[1..];
-        Some(Box::new(HuffmanNode::new(0, Some(symbol))))
-    } else {
-        let left = deserialize_tree(data);
-        let right = deserialize_tree(data);
-        Some(Box::new(HuffmanNode {
-            frequency: 0,
-            symbol: None,
-            left,
-            right,
-        }))
-    }
-}
-
-fn decode_data(compressed_data: &[u8], root: &Option<Box<HuffmanNode>>) -> Vec<u8> {
-    let mut decoded_data = Vec::new();
-    let mut current_node = root.clone();
-    let mut bit_buffer = 0u8;
-    let mut bit_count = 0;
-
-    for byte in compressed_data {
-        for i in (0..8).rev() {
-            let bit = (byte >> i) & 1;
-            bit_buffer = (bit_buffer << 1) | bit;
-            bit_count += 1;
-
-            if let Some(node) = &current_node {
-                if let Some(symbol) = node.symbol {
-                    decoded_data.push(symbol);
-                    current_node = root.clone();
-                } else {
-                    current_node = if bit_buffer & (1 << (bit_count - 1)) != 0 {
-                        node.right.clone()
-                    } else {
-                        node.left.clone()
-                    };
-                }
-            }
-            if bit_count == 8 {
-                bit_count = 0;
-                bit_buffer = 0;
-            }
+// `count` is the original length stored in the file header: decoding stops after that
+// many symbols, so the zero padding of the last byte can't produce extra ones. Running
+// out of bits first is Truncated, a path the tree doesn't have is InvalidCode.
+fn decode_data(compressed_data: &[u8], root: &Option<Box<HuffmanNode>>, count: usize) -> Result<Vec<u8>, HuffmanError> {
+    let mut bits = BitReader::new(compressed_data);
+    // Every code takes at least one bit, whatever count the header claims
+    let mut decoded = Vec::with_capacity(count.min(compressed_data.len() * 8));
+    while decoded.len() < count {
+        match decode_one(&mut bits, root) {
+            Some((symbol, _)) => decoded.push(symbol),
+            None if bits.remaining() == 0 => return Err(HuffmanError::Truncated),
+            None => return Err(HuffmanError::InvalidCode),
         }
     }
-    decoded_data
+    Ok(decoded)
 }
 
+// Both file formats start with the original length (u32, little endian), followed by
+// the code description and the packed payload:
+//   tree format:      serialize_tree output, then payload
+//   canonical format: 256 code lengths (one byte per symbol, 0 = unused), then payload
 
-fn main() {
-    let input_filename = "input.txt";
-    let data = read_binary_file(input_filename);
-    println!("Original data size: {} bytes", data.len());
+// (original length, codes, payload)
+type ParsedFile<'a> = (usize, BTreeMap<u8, String>, &'a [u8]);
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum HeaderFormat {
+    Tree,      // serialize_tree: 1 byte per internal node, 2 bytes per leaf
+    Canonical, // one code length per possible byte value
+    BitPacked, // same walk as Tree, with 1-bit node flags and 8-bit symbols
+}
+
+// Size of the code description for an alphabet of `distinct_symbols` symbols, not
+// counting the 4-byte original length every format starts with
+fn minimum_overhead_bytes(distinct_symbols: usize, format: HeaderFormat) -> usize {
+    if distinct_symbols == 0 {
+        return if format == HeaderFormat::Canonical { 256 } else { 0 };
+    }
+    let internal_nodes = distinct_symbols - 1;
+    match format {
+        HeaderFormat::Tree => internal_nodes + 2 * distinct_symbols,
+        HeaderFormat::Canonical => 256,
+        HeaderFormat::BitPacked => (internal_nodes + 9 * distinct_symbols).div_ceil(8),
+    }
+}
+
+fn code_lengths(codes: &BTreeMap<u8, String>) -> [u8; 256] {
+    let mut lengths = [0u8; 256];
+    for (&symbol, code) in codes {
+        lengths[symbol as usize] = code.len() as u8;
+    }
+    lengths
+}
+
+// Sum of 2^-length over the non-zero lengths, 1.0 for a full code tree
+fn kraft_sum(lengths: &[u8]) -> f64 {
+    lengths
+        .iter()
+        .filter(|&&length| length > 0)
+        .map(|&length| 2f64.powi(-(length as i32)))
+        .sum()
+}
+
+// Exact integer version of kraft_sum(lengths) <= 1.0, which rounding can get wrong for long
+// codes. Going from the longest codes up, every two slots of one length fill one slot of
+// the length above, rounding up.
+fn validate_kraft(lengths: &[u8]) -> bool {
+    let mut counts = [0usize; 256];
+    for &length in lengths.iter().filter(|&&length| length > 0) {
+        counts[length as usize] += 1;
+    }
+    let used = counts.iter().skip(1).rev().fold(0, |used: usize, &count| count + used.div_ceil(2));
+    used <= 2
+}
+
+// Canonical codes are built in a u64, so longer codes can't be represented
+const MAX_CODE_LENGTH: u8 = 64;
+
+// Canonical assignment: symbols sorted by (length, symbol) get consecutive codes
+fn codes_from_lengths(lengths: &[u8]) -> Result<BTreeMap<u8, String>, HuffmanError> {
+    if let Some(&length) = lengths.iter().find(|&&length| length > MAX_CODE_LENGTH) {
+        return Err(HuffmanError::CodeTooLong(length));
+    }
+    if !validate_kraft(lengths) {
+        return Err(HuffmanError::KraftViolation);
+    }
+    let mut symbols: Vec<(u8, u8)> = lengths
+        .iter()
+        .enumerate()
+        .filter(|&(_, &length)| length > 0)
+        .map(|(symbol, &length)| (length, symbol as u8))
+        .collect();
+    symbols.sort();
 
-    let frequencies = calculate_frequencies(&data);
-    let huffman_tree = build_huffman_tree(&frequencies);
+    let mut codes = BTreeMap::new();
+    let mut code = 0u64;
+    let mut previous_length = 0;
+    for (length, symbol) in symbols {
+        if previous_length != 0 {
+            code = (code + 1) << (length - previous_length);
+        }
+        previous_length = length;
+        codes.insert(symbol, format!("{:0width$b}", code, width = length as usize));
+    }
+    Ok(codes)
+}
+
+fn tree_from_codes(codes: &BTreeMap<u8, String>) -> Option<Box<HuffmanNode>> {
+    if codes.is_empty() {
+        return None;
+    }
+    // Undo generate_codes' one-bit code for a lone leaf
+    if codes.len() == 1
+        && let Some((&symbol, code)) = codes.first_key_value()
+        && code == "0"
+    {
+        return Some(Box::new(HuffmanNode::new(0, Some(symbol))));
+    }
+    let mut root = Box::new(HuffmanNode::new(0, None));
+    for (&symbol, code) in codes {
+        let mut node = &mut root;
+        for bit in code.chars() {
+            let child = if bit == '1' { &mut node.right } else { &mut node.left };
+            node = child.get_or_insert_with(|| Box::new(HuffmanNode::new(0, None)));
+        }
+        node.symbol = Some(symbol);
+    }
+    Some(root)
+}
+
+fn length_header(len: usize) -> Result<Vec<u8>, HuffmanError> {
+    let len = u32::try_from(len).map_err(|_| HuffmanError::InputTooLarge(len))?;
+    Ok(len.to_le_bytes().to_vec())
+}
+
+fn split_length(bytes: &[u8]) -> Result<(usize, &[u8]), HuffmanError> {
+    if bytes.len() < 4 {
+        return Err(HuffmanError::Truncated);
+    }
+    let (length, rest) = bytes.split_at(4);
+    Ok((u32::from_le_bytes(length.try_into().unwrap()) as usize, rest))
+}
+
+fn parse_tree_format(bytes: &[u8]) -> Result<ParsedFile<'_>, HuffmanError> {
+    let (count, mut rest) = split_length(bytes)?;
+    let tree = if count > 0 { Some(deserialize_tree(&mut rest)?) } else { None };
+    let mut codes = BTreeMap::new();
+    generate_codes(&tree, String::new(), &mut codes);
+    Ok((count, codes, rest))
+}
+
+fn parse_canonical_format(bytes: &[u8]) -> Result<ParsedFile<'_>, HuffmanError> {
+    let (count, rest) = split_length(bytes)?;
+    if rest.len() < 256 {
+        return Err(HuffmanError::Truncated);
+    }
+    let (lengths, payload) = rest.split_at(256);
+    Ok((count, codes_from_lengths(lengths)?, payload))
+}
+
+fn compress_tree_format(data: &[u8]) -> Result<Vec<u8>, HuffmanError> {
+    let huffman_tree = build_huffman_tree(&calculate_frequencies(data));
+    let mut codes = BTreeMap::new();
+    generate_codes(&huffman_tree, String::new(), &mut codes);
+
+    let mut output = length_header(data.len())?;
+    serialize_tree(&huffman_tree, &mut output);
+    output.extend(encode_data(data, &codes));
+    Ok(output)
+}
+
+fn compress_canonical_format(data: &[u8]) -> Result<Vec<u8>, HuffmanError> {
+    let huffman_tree = build_huffman_tree(&calculate_frequencies(data));
     let mut codes = BTreeMap::new();
     generate_codes(&huffman_tree, String::new(), &mut codes);
+    let lengths = code_lengths(&codes);
+
+    let mut output = length_header(data.len())?;
+    output.extend(lengths);
+    let canonical = codes_from_lengths(&lengths).expect("Huffman code lengths satisfy Kraft's inequality");
+    output.extend(encode_data(data, &canonical));
+    Ok(output)
+}
+
+fn decompress_tree_format(bytes: &[u8]) -> Result<Vec<u8>, HuffmanError> {
+    let (count, mut rest) = split_length(bytes)?;
+    let tree = if count > 0 { Some(deserialize_tree(&mut rest)?) } else { None };
+    decode_data(rest, &tree, count)
+}
+
+fn decompress_canonical_format(bytes: &[u8]) -> Result<Vec<u8>, HuffmanError> {
+    let (count, codes, payload) = parse_canonical_format(bytes)?;
+    decode_data(payload, &tree_from_codes(&codes), count)
+}
 
-    let mut tree_data = Vec::new();
-    serialize_tree(&huffman_tree, &mut tree_data);
+// Keeps the code lengths of the tree, so the payload size is unchanged. The bits
+// themselves are only reused verbatim when the tree already assigns canonical codes;
+// otherwise the payload is decoded and re-encoded with the canonical codes.
+fn transcode_to_canonical(bytes: &[u8]) -> Result<Vec<u8>, HuffmanError> {
+    let (count, codes, payload) = parse_tree_format(bytes)?;
+    let lengths = code_lengths(&codes);
+    let canonical = codes_from_lengths(&lengths)?;
 
-    let compressed_data = encode_data(&data, &codes);
-    let compressed_filename = "compressed.bin";
-    write_binary_file(compressed_filename, &tree_data, &compressed_data);
-    println!("Compressed data written to {}", compressed_filename);
-    // let compressed_file = read_binary_file("compressed.bin");
-    // println!("Compressed file content: {:?}", compressed_file);
+    let mut output = length_header(count)?;
+    output.extend(lengths);
+    if canonical == codes {
+        output.extend_from_slice(payload);
+    } else {
+        let data = decode_data(payload, &tree_from_codes(&codes), count)?;
+        output.extend(encode_data(&data, &canonical));
+    }
+    Ok(output)
+}
+
+// The tree is rebuilt from the canonical codes themselves, so the payload is always
+// copied verbatim.
+fn transcode_to_tree(bytes: &[u8]) -> Result<Vec<u8>, HuffmanError> {
+    let (count, codes, payload) = parse_canonical_format(bytes)?;
+    let mut output = length_header(count)?;
+    serialize_tree(&tree_from_codes(&codes), &mut output);
+    output.extend_from_slice(payload);
+    Ok(output)
+}
+
+
+// Whole-file versions of compress_tree_format / decompress_tree_format
+fn compress_file(input: &str, output: &str) -> io::Result<()> {
+    let data = read_binary_file(input)?;
+    write_binary_file(output, &[], &compress_tree_format(&data)?)
 }
 
+// Corrupt input comes back as an io::ErrorKind::InvalidData error
+fn decompress_file(input: &str, output: &str) -> io::Result<()> {
+    let decoded = decompress_tree_format(&read_binary_file(input)?)?;
+    write_binary_file(output, &[], &decoded)
+}
+
+#[derive(Debug, PartialEq)]
+enum Command {
+    Compress,
+    Decompress,
+}
+
+const USAGE: &str = "usage: huffman compress <input> <output>\n       huffman decompress <input> <output>";
+
+// Arguments after the program name: a command followed by the input and output paths
+fn parse_args(args: &[String]) -> Option<(Command, &str, &str)> {
+    let [command, input, output] = args else {
+        return None;
+    };
+    let command = match command.as_str() {
+        "compress" => Command::Compress,
+        "decompress" => Command::Decompress,
+        _ => return None,
+    };
+    Some((command, input, output))
+}
+
+fn run(command: Command, input: &str, output: &str) -> io::Result<()> {
+    match command {
+        Command::Compress => compress_file(input, output)?,
+        Command::Decompress => decompress_file(input, output)?,
+    }
+    let (before, after) = (std::fs::metadata(input)?.len(), std::fs::metadata(output)?.len());
+    println!("{} ({} bytes) -> {} ({} bytes)", input, before, output, after);
+    Ok(())
+}
+
+fn main() {
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    let Some((command, input, output)) = parse_args(&args) else {
+        eprintln!("{}", USAGE);
+        std::process::exit(2);
+    };
+    if let Err(e) = run(command, input, output) {
+        eprintln!("Error: {}", e);
+        std::process::exit(1);
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn codes_for(data: &[u8]) -> BTreeMap<u8, String> {
+        let tree = build_huffman_tree(&calculate_frequencies(data));
+        let mut codes = BTreeMap::new();
+        generate_codes(&tree, String::new(), &mut codes);
+        codes
+    }
+
+    #[test]
+    fn test_is_prefix_free() {
+        for input in [&b"abracadabra"[..], b"aaaabbbccd", b"the quick brown fox jumps over the lazy dog"] {
+            assert!(is_prefix_free(&codes_for(input)));
+        }
+
+        let mut codes = BTreeMap::new();
+        codes.insert(b'a', String::from("01"));
+        codes.insert(b'b', String::from("011"));
+        codes.insert(b'c', String::from("1"));
+        assert!(!is_prefix_free(&codes));
+    }
+
+    #[test]
+    fn test_optimality_gap() {
+        let dyadic = b"aaaabbcd";
+        assert!(optimality_gap(dyadic).abs() < 1e-12);
+
+        let skewed = b"aaaaabbbccde";
+        let gap = optimality_gap(skewed);
+        assert!(gap > 0.0 && gap < 0.05);
+    }
+
+    #[test]
+    fn test_transcode() {
+        let data = b"the quick brown fox jumps over the lazy dog";
+
+        let tree_file = compress_tree_format(data).unwrap();
+        assert_eq!(decompress_tree_format(&tree_file).unwrap(), data);
+
+        let canonical_file = transcode_to_canonical(&tree_file).unwrap();
+        assert_eq!(canonical_file, compress_canonical_format(data).unwrap());
+        assert_eq!(decompress_canonical_format(&canonical_file).unwrap(), data);
+
+        let back_to_tree = transcode_to_tree(&canonical_file).unwrap();
+        assert_eq!(decompress_tree_format(&back_to_tree).unwrap(), data);
+
+        assert_eq!(decompress_tree_format(&tree_file[..2]), Err(HuffmanError::Truncated));
+    }
+
+    #[test]
+    fn test_validate_kraft() {
+        let lengths = code_lengths(&codes_for(b"the quick brown fox jumps over the lazy dog"));
+        assert_eq!(kraft_sum(&lengths), 1.0);
+        assert!(validate_kraft(&lengths));
+        assert!(codes_from_lengths(&lengths).is_ok());
+
+        let oversubscribed = [1, 1, 2];
+        assert!(!validate_kraft(&oversubscribed));
+        assert_eq!(codes_from_lengths(&oversubscribed), Err(HuffmanError::KraftViolation));
+    }
+
+    #[test]
+    fn test_minimum_overhead_bytes() {
+        assert_eq!(minimum_overhead_bytes(256, HeaderFormat::Canonical), 256);
+        assert_eq!(minimum_overhead_bytes(256, HeaderFormat::Tree), 767);
+        assert_eq!(minimum_overhead_bytes(256, HeaderFormat::BitPacked), 320);
+
+        let data = b"the quick brown fox jumps over the lazy dog";
+        let distinct = calculate_frequencies(data).len();
+        let mut tree_data = Vec::new();
+        serialize_tree(&build_huffman_tree(&calculate_frequencies(data)), &mut tree_data);
+        assert_eq!(minimum_overhead_bytes(distinct, HeaderFormat::Tree), tree_data.len());
+    }
+
+    #[test]
+    fn test_encode_data_checked() {
+        let data = b"abracadabra";
+        let codes = codes_for(data);
+        assert_eq!(encode_data_checked(data, &codes).unwrap(), encode_data(data, &codes));
+        assert_eq!(encode_data_checked(b"abz", &codes), Err(HuffmanError::MissingCode(b'z')));
+
+        let mut bad = codes.clone();
+        bad.insert(b'z', format!("{}1", codes[&b'a']));
+        assert_eq!(encode_data_checked(data, &bad), Err(HuffmanError::NotPrefixFree));
+    }
+
+    #[test]
+    fn test_decode_one() {
+        let data = b"abracadabra";
+        let tree = build_huffman_tree(&calculate_frequencies(data));
+        let mut codes = BTreeMap::new();
+        generate_codes(&tree, String::new(), &mut codes);
+        let bit_len: usize = data.iter().map(|b| codes[b].len()).sum();
+        assert_ne!(bit_len % 8, 0);
+        let payload = encode_data(data, &codes);
+
+        let mut reader = BitReader::with_len(&payload, bit_len);
+        let mut decoded = Vec::new();
+        let mut consumed = 0;
+        while let Some((symbol, bits)) = decode_one(&mut reader, &tree) {
+            assert_eq!(bits, codes[&symbol].len());
+            decoded.push(symbol);
+            consumed += bits;
+        }
+        assert_eq!(decoded, decode_data(&payload, &tree, data.len()).unwrap());
+        assert_eq!(decoded, data);
+        assert_eq!(consumed, bit_len);
+        assert_eq!(reader.remaining(), 0);
+
+        assert_eq!(decode_one(&mut BitReader::new(&[]), &tree), None);
+    }
+
+    #[test]
+    fn test_compress_file() {
+        let dir = std::env::temp_dir();
+        let path = |name: &str| dir.join(format!("huffman_{}_{}", std::process::id(), name)).to_string_lossy().into_owned();
+        let (input, compressed, output) = (path("input"), path("compressed"), path("output"));
+
+        let data = "this is an example for huffman encoding 😊😊😊 aaa bfkzhjgsvfdnkqhfj\n".repeat(3);
+        std::fs::write(&input, &data).unwrap();
+        compress_file(&input, &compressed).unwrap();
+        let header = read_binary_file(&compressed).unwrap();
+        assert_eq!(header[..4], (data.len() as u32).to_le_bytes());
+        decompress_file(&compressed, &output).unwrap();
+        assert_eq!(read_binary_file(&output).unwrap(), data.as_bytes());
+
+        std::fs::write(&compressed, &header[..3]).unwrap();
+        assert_eq!(decompress_file(&compressed, &output).unwrap_err().kind(), io::ErrorKind::InvalidData);
+        std::fs::write(&compressed, [5, 0, 0, 0, 1]).unwrap();
+        assert_eq!(decompress_file(&compressed, &output).unwrap_err().kind(), io::ErrorKind::InvalidData);
+        for file in [input, compressed, output] {
+            std::fs::remove_file(file).unwrap();
+        }
+    }
+
+    #[test]
+    fn test_length_header() {
+        let data = b"abracadabra";
+        let codes = codes_for(data);
+        let bit_len: usize = data.iter().map(|b| codes[b].len()).sum();
+        assert_ne!(bit_len % 8, 0);
+
+        let file = compress_tree_format(data).unwrap();
+        assert_eq!(decompress_tree_format(&file).unwrap(), data);
+        let (count, mut rest) = split_length(&file).unwrap();
+        assert_eq!(count, data.len());
+        let tree = Some(deserialize_tree(&mut rest).unwrap());
+        assert_eq!(decode_data(rest, &tree, count).unwrap(), data);
+        assert!(decode_data(rest, &tree, 0).unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_decode_data() {
+        for input in [&b"abracadabra"[..], b"aaaabbbccd", b"the quick brown fox jumps over the lazy dog"] {
+            let tree = build_huffman_tree(&calculate_frequencies(input));
+            let payload = encode_data(input, &codes_for(input));
+            assert_eq!(decode_data(&payload, &tree, input.len()).unwrap(), input);
+            assert_eq!(decode_data(&payload, &tree, 3).unwrap(), &input[..3]);
+            assert_eq!(decode_data(&payload, &tree, payload.len() * 8 + 1), Err(HuffmanError::Truncated));
+        }
+        assert_eq!(decode_data(&[0xFF], &None, 4), Err(HuffmanError::InvalidCode));
+
+        let mut incomplete = BTreeMap::new();
+        incomplete.insert(b'a', String::from("0"));
+        incomplete.insert(b'b', String::from("10"));
+        assert_eq!(decode_data(&[0b0110_0000], &tree_from_codes(&incomplete), 3), Err(HuffmanError::InvalidCode));
+    }
+
+    #[test]
+    fn test_single_symbol() {
+        let data = b"aaaaaa";
+        let codes = codes_for(data);
+        assert_eq!(codes[&b'a'], "0");
+        let tree = build_huffman_tree(&calculate_frequencies(data));
+        assert_eq!(decode_data(&encode_data(data, &codes), &tree, data.len()).unwrap(), data);
+
+        let tree_file = compress_tree_format(data).unwrap();
+        assert_eq!(decompress_tree_format(&tree_file).unwrap(), data);
+        let canonical_file = compress_canonical_format(data).unwrap();
+        assert_eq!(decompress_canonical_format(&canonical_file).unwrap(), data);
+        assert_eq!(transcode_to_canonical(&tree_file).unwrap(), canonical_file);
+        assert_eq!(decompress_tree_format(&transcode_to_tree(&canonical_file).unwrap()).unwrap(), data);
+
+        assert_eq!(decompress_tree_format(&compress_tree_format(b"").unwrap()).unwrap(), b"");
+    }
+
+    #[test]
+    fn test_build_huffman_tree() {
+        // Optimal encoded size: the sum of the frequencies of every merged node
+        fn optimal_bits(frequencies: &[(u8, usize)]) -> usize {
+            let mut weights: Vec<usize> = frequencies.iter().map(|&(_, f)| f).collect();
+            let mut total = 0;
+            while weights.len() > 1 {
+                weights.sort_unstable_by(|a, b| b.cmp(a));
+                let merged = weights.pop().unwrap() + weights.pop().unwrap();
+                total += merged;
+                weights.push(merged);
+            }
+            total
+        }
+
+        let every_byte: Vec<u8> = (0..=255u8).flat_map(|b| std::iter::repeat_n(b, b as usize % 7 + 1)).collect();
+        for input in [&b"abracadabra"[..], b"the quick brown fox jumps over the lazy dog", &every_byte] {
+            let frequencies = calculate_frequencies(input);
+            let codes = codes_for(input);
+            assert!(is_prefix_free(&codes));
+            assert_eq!(kraft_sum(&code_lengths(&codes)), 1.0);
+            let bits: usize = input.iter().map(|b| codes[b].len()).sum();
+            assert_eq!(bits, optimal_bits(&frequencies));
+        }
+        assert!(build_huffman_tree(&[]).is_none());
+    }
+
+    #[test]
+    fn test_read_binary_file() {
+        let dir = std::env::temp_dir();
+        let path = |name: &str| dir.join(format!("huffman_{}_{}", std::process::id(), name)).to_string_lossy().into_owned();
+        let (missing, unused) = (path("missing"), path("unused"));
+
+        let error = read_binary_file(&missing).unwrap_err();
+        assert_eq!(error.kind(), io::ErrorKind::NotFound);
+        assert!(compress_file(&missing, &unused).is_err());
+        let created = std::path::Path::new(&unused).exists();
+        if created {
+            std::fs::remove_file(&unused).unwrap();
+        }
+        assert!(!created);
+    }
+
+    #[test]
+    fn test_parse_args() {
+        let args = |list: &[&str]| list.iter().map(|a| a.to_string()).collect::<Vec<String>>();
+        assert_eq!(parse_args(&args(&["compress", "input.txt", "out.bin"])), Some((Command::Compress, "input.txt", "out.bin")));
+        assert_eq!(parse_args(&args(&["decompress", "out.bin", "back.txt"])), Some((Command::Decompress, "out.bin", "back.txt")));
+        assert_eq!(parse_args(&args(&["compress", "input.txt"])), None);
+        assert_eq!(parse_args(&args(&["extract", "a", "b"])), None);
+        assert_eq!(parse_args(&args(&[])), None);
+    }
+
+    #[test]
+    fn test_deserialize_tree() {
+        let mut tree_data = Vec::new();
+        serialize_tree(&build_huffman_tree(&calculate_frequencies(b"abracadabra")), &mut tree_data);
+        for len in 0..tree_data.len() {
+            assert_eq!(deserialize_tree(&mut &tree_data[..len]).unwrap_err(), HuffmanError::Truncated);
+        }
+        let mut rest = tree_data.as_slice();
+        assert!(deserialize_tree(&mut rest).is_ok());
+        assert!(rest.is_empty());
+
+        assert_eq!(decompress_tree_format(&[5, 0, 0, 0, 1]), Err(HuffmanError::Truncated));
+        assert_eq!(decompress_tree_format(&[5, 0, 0, 0, 0, 1, b'a']), Err(HuffmanError::Truncated));
+
+        let mut hostile = 5u32.to_le_bytes().to_vec();
+        hostile.resize(2_000_000, 0);
+        assert_eq!(decompress_tree_format(&hostile), Err(HuffmanError::TreeTooDeep));
+
+        // Deepest valid shape: a chain of 255 internal nodes, one leaf hanging off each
+        let mut chain = Vec::new();
+        for symbol in 0..255u8 {
+            chain.extend([0, 1, symbol]);
+        }
+        chain.extend([1, 255]);
+        let mut rest = chain.as_slice();
+        assert!(deserialize_tree(&mut rest).is_ok());
+        assert!(rest.is_empty());
+    }
+
+    #[test]
+    fn test_code_too_long() {
+        let mut lengths = [0u8; 256];
+        lengths[b'a' as usize] = 1;
+        lengths[b'b' as usize] = 100;
+        assert!(validate_kraft(&lengths));
+        assert_eq!(codes_from_lengths(&lengths), Err(HuffmanError::CodeTooLong(100)));
+
+        let mut file = 2u32.to_le_bytes().to_vec();
+        file.extend(lengths);
+        file.push(0b0100_0000);
+        assert_eq!(decompress_canonical_format(&file), Err(HuffmanError::CodeTooLong(100)));
+        assert_eq!(transcode_to_tree(&file), Err(HuffmanError::CodeTooLong(100)));
+
+        lengths[b'b' as usize] = 64;
+        assert_eq!(codes_from_lengths(&lengths).unwrap()[&b'b'], format!("1{}", "0".repeat(63)));
+
+        // 1 + 2^-64 rounds to 1.0 in floating point
+        let oversubscribed = [1, 1, 64];
+        assert!(!validate_kraft(&oversubscribed));
+        assert_eq!(codes_from_lengths(&oversubscribed), Err(HuffmanError::KraftViolation));
+    }
+
+    #[test]
+    fn test_length_header_limits() {
+        assert_eq!(length_header(5).unwrap(), vec![5, 0, 0, 0]);
+        assert_eq!(length_header(u32::MAX as usize).unwrap(), vec![0xFF; 4]);
+        let too_large = u32::MAX as usize + 1;
+        assert_eq!(length_header(too_large), Err(HuffmanError::InputTooLarge(too_large)));
+
+        // A header claiming ~4 GiB over a one-byte payload fails without allocating for it
+        let mut file = vec![0xFF; 4];
+        serialize_tree(&build_huffman_tree(&calculate_frequencies(b"ab")), &mut file);
+        file.push(0b0101_0101);
+        assert_eq!(decompress_tree_format(&file), Err(HuffmanError::Truncated));
+    }
+}
@@ -0,0 +1,50 @@
+use plotters::prelude::*;
+use math::geometry::Point;
+
+fn main() -> Result<(), Box<dyn std::error::Error>> {
+    let point = Point { x: 0.0, y: 0.0 };
+
+    let root = BitMapBackend::new("vector_trajectory.png", (640, 480)).into_drawing_area();
+    root.fill(&WHITE)?;
+
+    let mut chart = ChartBuilder::on(&root)
+        .caption("Trajectoire du vecteur", ("sans-serif", 30))
+        .build_cartesian_2d(-15..15, -15..15)?;
+
+    chart.configure_mesh().draw()?;
+
+    let red_style = ShapeStyle {
+        color: RED.to_rgba(),
+        filled: true,
+        stroke_width: 0,
+    };
+
+    // Tracer le point (0, 0)
+    chart.draw_series(std::iter::once(Circle::new(
+        (0, 0),
+        5,
+        red_style,
+    )))?;
+
+    let vectors = vec![
+        Point { x: -5.0, y: 6.0 },
+        Point { x: 7.0, y: -3.0 },
+        Point { x: 4.0, y: 9.0 },
+    ];
+
+    for vector in vectors {
+        chart.draw_series(LineSeries::new(
+                vec![
+                (point.x as i32, point.y as i32),
+                (vector.x as i32, vector.y as i32),
+                ],
+                &BLUE,
+        ))?;
+    }
+
+    // Sauvegarder le graphique
+    root.present()?;
+
+    Ok(())
+}
+
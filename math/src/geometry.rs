@@ -0,0 +1,2156 @@
+use std::ops::{Add, Sub, Mul, Div, Neg, AddAssign, SubAssign};
+use std::f64::consts::PI;
+use std::fmt;
+
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Point<T> {
+    pub x: T,
+    pub y: T,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Point3<T> {
+    pub x: T,
+    pub y: T,
+    pub z: T,
+}
+
+// Conversion vers f64 pour norm, normalize et distance. Into<f64> n'existe pas pour i64,
+// u64, isize ni usize, d'où ce trait qui convertit avec `as`.
+pub trait ToF64 {
+    fn to_f64(self) -> f64;
+}
+
+macro_rules! impl_to_f64 {
+    ($($t:ty),*) => {
+        $(impl ToF64 for $t {
+            fn to_f64(self) -> f64 {
+                self as f64
+            }
+        })*
+    };
+}
+
+impl_to_f64!(f32, f64, i8, i16, i32, i64, isize, u8, u16, u32, u64, usize);
+
+impl<T: fmt::Display> fmt::Display for Point<T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "({}, {})", self.x, self.y)
+    }
+}
+
+impl<T: Add<Output = T>> Add for Point<T> {
+    type Output = Point<T>;
+
+    fn add(self, other: Point<T>) -> Point<T> {
+        Point {
+            x: self.x + other.x,
+            y: self.y + other.y,
+        }
+    }
+}
+
+impl<T: Sub<Output = T>> Sub for Point<T> {
+    type Output = Point<T>;
+
+    fn sub(self, other: Point<T>) -> Point<T> {
+        Point {
+            x: self.x - other.x,
+            y: self.y - other.y,
+        }
+    }
+}
+
+impl<T: Mul<Output = T> + Copy> Mul<T> for Point<T> {
+    type Output = Point<T>;
+
+    fn mul(self, scalar: T) -> Point<T> {
+        Point {
+            x: self.x * scalar,
+            y: self.y * scalar,
+        }
+    }
+}
+
+impl<T: Div<Output = T> + Copy> Div<T> for Point<T> {
+    type Output = Point<T>;
+
+    fn div(self, scalar: T) -> Point<T> {
+        Point {
+            x: self.x / scalar,
+            y: self.y / scalar,
+        }
+    }
+}
+
+impl<T: Neg<Output = T>> Neg for Point<T> {
+    type Output = Point<T>;
+
+    fn neg(self) -> Point<T> {
+        Point {
+            x: -self.x,
+            y: -self.y,
+        }
+    }
+}
+
+impl<T: AddAssign> AddAssign for Point<T> {
+    fn add_assign(&mut self, other: Point<T>) {
+        self.x += other.x;
+        self.y += other.y;
+    }
+}
+
+impl<T: SubAssign> SubAssign for Point<T> {
+    fn sub_assign(&mut self, other: Point<T>) {
+        self.x -= other.x;
+        self.y -= other.y;
+    }
+}
+
+impl<T> From<(T, T)> for Point<T> {
+    fn from((x, y): (T, T)) -> Self {
+        Point { x, y }
+    }
+}
+
+impl<T> From<Point<T>> for (T, T) {
+    fn from(p: Point<T>) -> Self {
+        (p.x, p.y)
+    }
+}
+
+impl<T: Mul<Output = T> + Add<Output = T> + Copy> Point<T> {
+    pub fn dot(&self, other: &Point<T>) -> T {
+        self.x * other.x + self.y * other.y
+    }
+}
+
+impl<T: Mul<Output = T> + Add<Output = T> + Copy + ToF64> Point<T> {
+    pub fn norm(&self) -> f64 {
+        let sum: f64 = (self.x * self.x + self.y * self.y).to_f64();
+        sum.sqrt()
+    }
+}
+
+impl<T: Mul<Output = T> + Add<Output = T> + Copy + ToF64> Point<T> {
+    pub fn normalize(&self) -> Point<f64> {
+        let length = self.norm();
+        Point {
+            x: self.x.to_f64() / length,
+            y: self.y.to_f64() / length,
+        }
+    }
+}
+
+impl<T: Mul<Output = T> + Sub<Output = T> + Copy> Point<T> {
+    pub fn cross(&self, other: &Point<T>) -> T {
+        self.x * other.y - self.y * other.x
+    }
+}
+
+impl Point<f64> {
+    pub fn rotate(&self, angle: f64) -> Point<f64> {
+        let rad = angle * PI / 180.0;
+        Point {
+            x: self.x * rad.cos() - self.y * rad.sin(),
+            y: self.x * rad.sin() + self.y * rad.cos(),
+        }
+    }
+
+    pub fn midpoint(&self, other: &Point<f64>) -> Point<f64> {
+        self.lerp(other, 0.5)
+    }
+
+    // self pour t = 0, other pour t = 1, extrapole en dehors de [0, 1]
+    pub fn lerp(&self, other: &Point<f64>, t: f64) -> Point<f64> {
+        self.clone() + (other.clone() - self.clone()) * t
+    }
+
+    // Rotation (en degrés) autour de pivot plutôt que de l'origine
+    pub fn rotate_around(&self, pivot: &Point<f64>, angle_degrees: f64) -> Point<f64> {
+        (self.clone() - pivot.clone()).rotate(angle_degrees) + pivot.clone()
+    }
+
+    // Égalité à eps près sur chaque coordonnée (rotate et normalize introduisent des erreurs d'arrondi)
+    pub fn approx_eq(&self, other: &Point<f64>, eps: f64) -> bool {
+        (self.x - other.x).abs() <= eps && (self.y - other.y).abs() <= eps
+    }
+}
+
+impl<T: Sub<Output = T> + Mul<Output = T> + Copy + ToF64> Point<T> {
+    pub fn distance(&self, other: &Point<T>) -> f64 {
+        let dx = (self.x - other.x).to_f64();
+        let dy = (self.y - other.y).to_f64();
+        (dx * dx + dy * dy).sqrt()
+    }
+
+    pub fn manhattan_distance(&self, other: &Point<T>) -> f64 {
+        let dx: f64 = (self.x - other.x).to_f64();
+        let dy: f64 = (self.y - other.y).to_f64();
+        dx.abs() + dy.abs()
+    }
+
+    pub fn chebyshev_distance(&self, other: &Point<T>) -> f64 {
+        let dx: f64 = (self.x - other.x).to_f64();
+        let dy: f64 = (self.y - other.y).to_f64();
+        dx.abs().max(dy.abs())
+    }
+}
+
+impl Point<f64> {
+    pub fn angle_with(&self, other: &Point<f64>) -> f64 {
+        let dot_product = self.dot(other);
+        let norms = self.norm() * other.norm();
+        (dot_product / norms).acos().to_degrees()
+    }
+
+    // Angle orienté (en degrés, dans ]-180, 180]) pour aller de self vers other
+    pub fn signed_angle_with(&self, other: &Point<f64>) -> f64 {
+        let angle = self.cross(other).atan2(self.dot(other)).to_degrees();
+        if angle <= -180.0 { angle + 360.0 } else { angle }
+    }
+
+    pub fn project_on(&self, other: &Point<f64>) -> Point<f64> {
+        let scalar = self.dot(other) / other.dot(other);
+        other.clone() * scalar
+    }
+
+    // (rayon, angle en radians dans ]-π, π])
+    pub fn to_polar(&self) -> (f64, f64) {
+        (self.norm(), self.y.atan2(self.x))
+    }
+
+    pub fn from_polar(radius: f64, angle_radians: f64) -> Point<f64> {
+        Point { x: radius * angle_radians.cos(), y: radius * angle_radians.sin() }
+    }
+
+    // Composante perpendiculaire à other : project_on + reject_from redonnent self
+    pub fn reject_from(&self, other: &Point<f64>) -> Point<f64> {
+        self.clone() - self.project_on(other)
+    }
+
+    // Symétrique par rapport à la droite (ab). Si a et b sont confondus, symétrique par rapport à a.
+    pub fn reflect_across_line(&self, a: &Point<f64>, b: &Point<f64>) -> Point<f64> {
+        let ab = b.clone() - a.clone();
+        let foot = if ab.dot(&ab) == 0.0 {
+            a.clone()
+        } else {
+            a.clone() + (self.clone() - a.clone()).project_on(&ab)
+        };
+        foot * 2.0 - self.clone()
+    }
+    
+    pub fn transform(&self, matrix: [[f64; 2]; 2]) -> Point<f64> {
+        Point {
+            x: matrix[0][0] * self.x + matrix[0][1] * self.y,
+            y: matrix[1][0] * self.x + matrix[1][1] * self.y,
+        }
+    }
+
+    // Le point est pris en coordonnées homogènes (x, y, 1) ; on divise par w s'il ne vaut pas 1
+    pub fn transform_affine(&self, matrix: [[f64; 3]; 3]) -> Point<f64> {
+        let apply = |row: [f64; 3]| row[0] * self.x + row[1] * self.y + row[2];
+        let (x, y, w) = (apply(matrix[0]), apply(matrix[1]), apply(matrix[2]));
+        if w == 1.0 { Point { x, y } } else { Point { x: x / w, y: y / w } }
+    }
+    
+    pub fn is_intersecting(p1: &Point<f64>, p2: &Point<f64>, q1: &Point<f64>, q2: &Point<f64>) -> bool {
+        let cross1 = (q1.x - p1.x) * (p2.y - p1.y) - (q1.y - p1.y) * (p2.x - p1.x);
+        let cross2 = (q2.x - p1.x) * (p2.y - p1.y) - (q2.y - p1.y) * (p2.x - p1.x);
+        let cross3 = (p1.x - q1.x) * (q2.y - q1.y) - (p1.y - q1.y) * (q2.x - q1.x);
+        let cross4 = (p2.x - q1.x) * (q2.y - q1.y) - (p2.y - q1.y) * (q2.x - q1.x);
+
+        (cross1 * cross2 < 0.0) && (cross3 * cross4 < 0.0)
+    }
+
+    // Côtés (sommet, sommet suivant) du polygone, y compris celui qui le referme ; rien pour
+    // moins de deux points
+    pub fn polygon_edges(polygon: &[Point<f64>]) -> impl Iterator<Item = (&Point<f64>, &Point<f64>)> {
+        let n = if polygon.len() < 2 { 0 } else { polygon.len() };
+        (0..n).map(move |i| (&polygon[i], &polygon[(i + 1) % n]))
+    }
+
+    // Lancer de rayon ; un point situé sur un bord (à 1e-10 près) est considéré à l'intérieur
+    pub fn is_inside_polygon(point: &Point<f64>, polygon: &[Point<f64>]) -> bool {
+        let mut count = 0;
+        for (a, b) in Point::polygon_edges(polygon) {
+            if Point::point_on_segment(point, a, b, 1e-10) {
+                return true;
+            }
+            // Un bord horizontal ne peut pas traverser le rayon : on ne divise jamais par zéro
+            if (a.y > point.y) != (b.y > point.y) && b.y != a.y {
+                let x_cross = (b.x - a.x) * (point.y - a.y) / (b.y - a.y) + a.x;
+                if point.x < x_cross {
+                    count += 1;
+                }
+            }
+        }
+        count % 2 == 1
+    }
+    
+    // Nombre de tours que fait le polygone autour du point, positif dans le sens trigonométrique
+    pub fn winding_number(point: &Point<f64>, polygon: &[Point<f64>]) -> i32 {
+        let mut winding = 0;
+        for (a, b) in Point::polygon_edges(polygon) {
+            if a.y <= point.y {
+                if b.y > point.y && Point::orientation(a, b, point) > 0.0 {
+                    winding += 1;
+                }
+            } else if b.y <= point.y && Point::orientation(a, b, point) < 0.0 {
+                winding -= 1;
+            }
+        }
+        winding
+    }
+
+    // Règle non nulle : contrairement à is_inside_polygon (pair-impair), les zones qu'un polygone
+    // auto-intersecté recouvre deux fois restent à l'intérieur. Les bords comptent comme dedans.
+    pub fn is_inside_polygon_nonzero(point: &Point<f64>, polygon: &[Point<f64>]) -> bool {
+        Point::polygon_edges(polygon).any(|(a, b)| Point::point_on_segment(point, a, b, 1e-10))
+            || Point::winding_number(point, polygon) != 0
+    }
+
+    pub fn is_collinear(p1: &Point<f64>, p2: &Point<f64>, p3: &Point<f64>) -> bool {
+        Point::orientation(p1, p2, p3).abs() < 1e-10
+    }
+
+    // Coordonnées (u, v, w) de p dans le triangle abc, telles que p = u·a + v·b + w·c et
+    // u + v + w = 1. Pour un triangle dégénéré (aire nulle) les trois valent NaN.
+    pub fn barycentric(p: &Point<f64>, a: &Point<f64>, b: &Point<f64>, c: &Point<f64>) -> (f64, f64, f64) {
+        let area = Point::orientation(a, b, c);
+        if area.abs() < 1e-12 {
+            return (f64::NAN, f64::NAN, f64::NAN);
+        }
+        let u = Point::orientation(p, b, c) / area;
+        let v = Point::orientation(a, p, c) / area;
+        (u, v, 1.0 - u - v)
+    }
+
+    // Bords compris ; toujours faux pour un triangle dégénéré
+    pub fn point_in_triangle(p: &Point<f64>, a: &Point<f64>, b: &Point<f64>, c: &Point<f64>) -> bool {
+        let (u, v, w) = Point::barycentric(p, a, b, c);
+        let eps = 1e-12;
+        u >= -eps && v >= -eps && w >= -eps
+    }
+
+    // Pied de la perpendiculaire sur [a, b], ramené sur le segment
+    pub fn closest_point_on_segment(&self, a: &Point<f64>, b: &Point<f64>) -> Point<f64> {
+        let ab = b.clone() - a.clone();
+        let length_squared = ab.dot(&ab);
+        if length_squared == 0.0 {
+            return a.clone();
+        }
+        let t = ((self.clone() - a.clone()).dot(&ab) / length_squared).clamp(0.0, 1.0);
+        a.clone() + ab * t
+    }
+
+    pub fn distance_to_segment(&self, a: &Point<f64>, b: &Point<f64>) -> f64 {
+        self.distance(&self.closest_point_on_segment(a, b))
+    }
+
+    // Vrai si p est sur le segment [a, b] (et pas seulement sur la droite), à epsilon près
+    pub fn point_on_segment(p: &Point<f64>, a: &Point<f64>, b: &Point<f64>, epsilon: f64) -> bool {
+        let ab = b.clone() - a.clone();
+        let ap = p.clone() - a.clone();
+        let length = ab.norm();
+        if length < epsilon {
+            return p.distance(a) <= epsilon;
+        }
+        let along = ap.dot(&ab) / length;
+        let across = ab.cross(&ap).abs() / length;
+        across <= epsilon && along >= -epsilon && along <= length + epsilon
+    }
+
+    // > 0 si p1, p2, p3 tournent dans le sens trigonométrique, < 0 sinon, 0 si alignés
+    pub fn orientation(p1: &Point<f64>, p2: &Point<f64>, p3: &Point<f64>) -> f64 {
+        (p2.x - p1.x) * (p3.y - p1.y) - (p2.y - p1.y) * (p3.x - p1.x)
+    }
+
+    // Seul l'alignement de trois points est vérifié, pas la cocyclicité
+    pub fn is_general_position(points: &[Point<f64>]) -> bool {
+        for i in 0..points.len() {
+            for j in i + 1..points.len() {
+                for k in j + 1..points.len() {
+                    if Point::is_collinear(&points[i], &points[j], &points[k]) {
+                        return false;
+                    }
+                }
+            }
+        }
+        true
+    }
+
+    pub fn nearest(&self, points: &[Point<f64>]) -> Option<(usize, f64)> {
+        points
+            .iter()
+            .map(|p| self.distance(p))
+            .enumerate()
+            .min_by(|a, b| a.1.total_cmp(&b.1))
+    }
+
+    // Renvoie f64::INFINITY si l'un des deux ensembles est vide
+    pub fn hausdorff_distance(a: &[Point<f64>], b: &[Point<f64>]) -> f64 {
+        if a.is_empty() || b.is_empty() {
+            return f64::INFINITY;
+        }
+        let directed = |from: &[Point<f64>], to: &[Point<f64>]| {
+            from.iter()
+                .filter_map(|p| p.nearest(to))
+                .map(|(_, d)| d)
+                .fold(0.0, f64::max)
+        };
+        directed(a, b).max(directed(b, a))
+    }
+
+    // Coins (min, max) de la boîte englobante alignée sur les axes, None si points est vide
+    pub fn bounding_box(points: &[Point<f64>]) -> Option<(Point<f64>, Point<f64>)> {
+        let first = points.first()?;
+        let mut min = first.clone();
+        let mut max = first.clone();
+        for p in &points[1..] {
+            min.x = min.x.min(p.x);
+            min.y = min.y.min(p.y);
+            max.x = max.x.max(p.x);
+            max.y = max.y.max(p.y);
+        }
+        Some((min, max))
+    }
+
+    pub fn vertex_average(points: &[Point<f64>]) -> Point<f64> {
+        let n = points.len() as f64;
+        let sum = points.iter().fold(Point { x: 0.0, y: 0.0 }, |acc, p| acc + p.clone());
+        sum / n
+    }
+
+    // Angle (en degrés) de la rotation autour des centroïdes qui amène `a` sur `b`.
+    // Les deux polygones doivent avoir le même nombre de sommets, dans le même ordre.
+    pub fn best_alignment_angle(a: &[Point<f64>], b: &[Point<f64>]) -> f64 {
+        assert_eq!(a.len(), b.len(), "polygons must have the same vertex count");
+        let ca = Point::vertex_average(a);
+        let cb = Point::vertex_average(b);
+        let (mut sin_sum, mut cos_sum) = (0.0, 0.0);
+        for (p, q) in a.iter().zip(b) {
+            let u = p.clone() - ca.clone();
+            let v = q.clone() - cb.clone();
+            sin_sum += u.cross(&v);
+            cos_sum += u.dot(&v);
+        }
+        sin_sum.atan2(cos_sum).to_degrees()
+    }
+
+    // Point d'impact le plus proche d'un rayon (origine, direction) sur un ensemble de segments
+    pub fn ray_cast(origin: &Point<f64>, dir: &Point<f64>, segments: &[(Point<f64>, Point<f64>)]) -> Option<Point<f64>> {
+        let mut best: Option<f64> = None;
+        for (a, b) in segments {
+            let edge = b.clone() - a.clone();
+            let denom = dir.cross(&edge);
+            if denom.abs() < 1e-12 {
+                continue;
+            }
+            let diff = a.clone() - origin.clone();
+            let t = diff.cross(&edge) / denom;
+            let u = diff.cross(dir) / denom;
+            if t >= 0.0 && (0.0..=1.0).contains(&u) && best.is_none_or(|b| t < b) {
+                best = Some(t);
+            }
+        }
+        best.map(|t| origin.clone() + dir.clone() * t)
+    }
+
+    // Sans obstacle, renvoie un vecteur vide. Sinon la scène est bornée par une boîte
+    // englobant les obstacles et le point de vue (avec une marge), et le polygone visible
+    // est renvoyé trié par angle croissant autour du point de vue.
+    pub fn visibility_polygon(viewpoint: &Point<f64>, obstacles: &[(Point<f64>, Point<f64>)]) -> Vec<Point<f64>> {
+        if obstacles.is_empty() {
+            return Vec::new();
+        }
+
+        let (mut min_x, mut min_y, mut max_x, mut max_y) = (viewpoint.x, viewpoint.y, viewpoint.x, viewpoint.y);
+        for (a, b) in obstacles {
+            for p in [a, b] {
+                min_x = min_x.min(p.x);
+                min_y = min_y.min(p.y);
+                max_x = max_x.max(p.x);
+                max_y = max_y.max(p.y);
+            }
+        }
+        let margin = (max_x - min_x).max(max_y - min_y) + 1.0;
+        let corners = [
+            Point { x: min_x - margin, y: min_y - margin },
+            Point { x: max_x + margin, y: min_y - margin },
+            Point { x: max_x + margin, y: max_y + margin },
+            Point { x: min_x - margin, y: max_y + margin },
+        ];
+        let mut segments = obstacles.to_vec();
+        for i in 0..4 {
+            segments.push((corners[i].clone(), corners[(i + 1) % 4].clone()));
+        }
+
+        let mut angles = Vec::new();
+        for (a, b) in &segments {
+            for p in [a, b] {
+                let angle = (p.y - viewpoint.y).atan2(p.x - viewpoint.x);
+                angles.extend([angle - 1e-4, angle, angle + 1e-4]);
+            }
+        }
+        angles.sort_by(|a, b| a.total_cmp(b));
+        angles.dedup();
+
+        angles
+            .iter()
+            .filter_map(|&angle| {
+                let dir = Point { x: angle.cos(), y: angle.sin() };
+                Point::ray_cast(viewpoint, &dir, &segments)
+            })
+            .collect()
+    }
+
+    pub fn snap_to_grid(points: &[Point<f64>], cell_size: f64) -> Vec<Point<f64>> {
+        points
+            .iter()
+            .map(|p| Point {
+                x: (p.x / cell_size).round() * cell_size,
+                y: (p.y / cell_size).round() * cell_size,
+            })
+            .collect()
+    }
+
+    // Aligne sur la grille puis retire les doublons consécutifs
+    pub fn dedup_snapped(points: &[Point<f64>], cell_size: f64) -> Vec<Point<f64>> {
+        let mut snapped = Point::snap_to_grid(points, cell_size);
+        snapped.dedup_by(|a, b| a.x == b.x && a.y == b.y);
+        snapped
+    }
+
+    // Formule du lacet : positive dans le sens trigonométrique, négative sinon
+    pub fn signed_polygon_area(polygon: &[Point<f64>]) -> f64 {
+        if polygon.len() < 3 {
+            return 0.0;
+        }
+        Point::polygon_edges(polygon).map(|(a, b)| a.cross(b)).sum::<f64>() / 2.0
+    }
+
+    pub fn polygon_area(polygon: &[Point<f64>]) -> f64 {
+        Point::signed_polygon_area(polygon).abs()
+    }
+
+    // Somme des côtés, y compris celui qui referme le polygone du dernier sommet au premier
+    pub fn polygon_perimeter(polygon: &[Point<f64>]) -> f64 {
+        Point::polygon_edges(polygon).map(|(a, b)| a.distance(b)).sum()
+    }
+
+    // Centre de gravité de la surface (formule de l'aire signée), None si l'aire est nulle
+    pub fn polygon_centroid(polygon: &[Point<f64>]) -> Option<Point<f64>> {
+        let area = Point::signed_polygon_area(polygon);
+        if area.abs() < 1e-12 {
+            return None;
+        }
+        let mut sum = Point { x: 0.0, y: 0.0 };
+        for (a, b) in Point::polygon_edges(polygon) {
+            sum += (a.clone() + b.clone()) * a.cross(b);
+        }
+        Some(sum / (6.0 * area))
+    }
+
+    // Moyenne des centroïdes pondérée par l'aire, les polygones d'aire nulle sont ignorés
+    pub fn multi_polygon_centroid(polygons: &[Vec<Point<f64>>]) -> Option<Point<f64>> {
+        let mut weighted = Point { x: 0.0, y: 0.0 };
+        let mut total_area = 0.0;
+        for polygon in polygons {
+            if let Some(centroid) = Point::polygon_centroid(polygon) {
+                let area = Point::polygon_area(polygon);
+                weighted += centroid * area;
+                total_area += area;
+            }
+        }
+        if total_area > 0.0 { Some(weighted / total_area) } else { None }
+    }
+
+    // Direction unitaire en chaque sommet : différence avant/arrière aux extrémités, moyenne
+    // des deux segments voisins ailleurs. Un chemin d'un seul point donne un vecteur nul.
+    pub fn tangents(path: &[Point<f64>]) -> Vec<Point<f64>> {
+        if path.len() < 2 {
+            return path.iter().map(|_| Point { x: 0.0, y: 0.0 }).collect();
+        }
+        // Un segment de longueur nulle (points répétés) compte comme un vecteur nul
+        let segments: Vec<Point<f64>> = path
+            .windows(2)
+            .map(|w| {
+                let d = w[1].clone() - w[0].clone();
+                if d.norm() < 1e-12 { Point { x: 0.0, y: 0.0 } } else { d.normalize() }
+            })
+            .collect();
+        let mut result = vec![segments[0].clone()];
+        for pair in segments.windows(2) {
+            let sum = pair[0].clone() + pair[1].clone();
+            // Demi-tour ou segments nuls : on garde la direction d'un seul segment
+            result.push(if sum.norm() < 1e-12 {
+                if pair[0].norm() > 0.0 { pair[0].clone() } else { pair[1].clone() }
+            } else {
+                sum.normalize()
+            });
+        }
+        result.push(segments[segments.len() - 1].clone());
+        result
+    }
+
+    // Courbe de Bézier cubique évaluée en t (p0 pour t = 0, p3 pour t = 1)
+    pub fn cubic_bezier(p0: &Point<f64>, p1: &Point<f64>, p2: &Point<f64>, p3: &Point<f64>, t: f64) -> Point<f64> {
+        let u = 1.0 - t;
+        p0.clone() * (u * u * u)
+            + p1.clone() * (3.0 * u * u * t)
+            + p2.clone() * (3.0 * u * t * t)
+            + p3.clone() * (t * t * t)
+    }
+
+    // Polyligne de steps segments (steps + 1 points) qui approche la courbe
+    pub fn sample_bezier(p0: &Point<f64>, p1: &Point<f64>, p2: &Point<f64>, p3: &Point<f64>, steps: usize) -> Vec<Point<f64>> {
+        let steps = steps.max(1);
+        (0..=steps)
+            .map(|i| Point::cubic_bezier(p0, p1, p2, p3, i as f64 / steps as f64))
+            .collect()
+    }
+
+    // Spline de Catmull–Rom uniforme passant par tous les points de passage. Le premier et le
+    // dernier point sont dupliqués pour fixer les tangentes aux extrémités. Chaque segment donne
+    // samples_per_segment points, puis le dernier point de passage termine la courbe.
+    pub fn catmull_rom(points: &[Point<f64>], samples_per_segment: usize) -> Vec<Point<f64>> {
+        if points.len() < 2 {
+            return points.to_vec();
+        }
+        let samples = samples_per_segment.max(1);
+        let at = |i: isize| points[i.clamp(0, points.len() as isize - 1) as usize].clone();
+        let mut curve = Vec::with_capacity((points.len() - 1) * samples + 1);
+        for i in 0..points.len() as isize - 1 {
+            let (p0, p1, p2, p3) = (at(i - 1), at(i), at(i + 1), at(i + 2));
+            for k in 0..samples {
+                let t = k as f64 / samples as f64;
+                let (t2, t3) = (t * t, t * t * t);
+                let point = p1.clone() * 2.0
+                    + (p2.clone() - p0.clone()) * t
+                    + (p0.clone() * 2.0 - p1.clone() * 5.0 + p2.clone() * 4.0 - p3.clone()) * t2
+                    + (p1.clone() * 3.0 - p0.clone() - p2.clone() * 3.0 + p3.clone()) * t3;
+                curve.push(point * 0.5);
+            }
+        }
+        curve.push(points[points.len() - 1].clone());
+        curve
+    }
+
+    // Douglas–Peucker : garde les extrémités et, récursivement, le point le plus éloigné de la
+    // corde tant que cet écart dépasse epsilon
+    pub fn simplify(points: &[Point<f64>], epsilon: f64) -> Vec<Point<f64>> {
+        if points.len() < 3 {
+            return points.to_vec();
+        }
+        let (first, last) = (&points[0], &points[points.len() - 1]);
+        let (index, max_distance) = points[1..points.len() - 1]
+            .iter()
+            .map(|p| p.distance_to_segment(first, last))
+            .enumerate()
+            .fold((0, 0.0), |best, (i, d)| if d > best.1 { (i + 1, d) } else { best });
+
+        if max_distance <= epsilon {
+            return vec![first.clone(), last.clone()];
+        }
+        let mut result = Point::simplify(&points[..=index], epsilon);
+        result.pop();
+        result.extend(Point::simplify(&points[index..], epsilon));
+        result
+    }
+
+    // Décale la polyligne de `distance` vers la gauche du sens de parcours (vers la droite si
+    // distance < 0), avec un raccord en onglet à chaque sommet intérieur
+    pub fn offset_polyline(path: &[Point<f64>], distance: f64) -> Vec<Point<f64>> {
+        if path.len() < 2 {
+            return path.to_vec();
+        }
+        let normals: Vec<Point<f64>> = path
+            .windows(2)
+            .map(|w| {
+                let d = (w[1].clone() - w[0].clone()).normalize();
+                Point { x: -d.y, y: d.x }
+            })
+            .collect();
+
+        let mut result = vec![path[0].clone() + normals[0].clone() * distance];
+        for (i, pair) in normals.windows(2).enumerate() {
+            let sum = pair[0].clone() + pair[1].clone();
+            let offset = if sum.norm() < 1e-12 {
+                pair[0].clone() * distance
+            } else {
+                let miter = sum.normalize();
+                miter.clone() * (distance / miter.dot(&pair[0]))
+            };
+            result.push(path[i + 1].clone() + offset);
+        }
+        result.push(path[path.len() - 1].clone() + normals[normals.len() - 1].clone() * distance);
+        result
+    }
+
+    // Variation de l'aire signée (positive dans le sens trigonométrique) quand `new_vertex` est
+    // inséré entre polygon[insert_after] et le sommet suivant. Seuls les termes du shoelace
+    // liés à l'arête coupée changent.
+    pub fn area_delta_on_insert(polygon: &[Point<f64>], insert_after: usize, new_vertex: &Point<f64>) -> f64 {
+        let a = &polygon[insert_after];
+        let b = &polygon[(insert_after + 1) % polygon.len()];
+        (a.cross(new_vertex) + new_vertex.cross(b) - a.cross(b)) / 2.0
+    }
+
+    // Les trous sont supposés entièrement contenus dans le contour extérieur
+    pub fn polygon_with_holes_area(outer: &[Point<f64>], holes: &[Vec<Point<f64>>]) -> f64 {
+        let holes_area: f64 = holes.iter().map(|hole| Point::polygon_area(hole)).sum();
+        Point::polygon_area(outer) - holes_area
+    }
+
+    pub fn circle_to_polygon(center: &Point<f64>, radius: f64, segments: usize) -> Vec<Point<f64>> {
+        (0..segments)
+            .map(|i| {
+                let angle = 2.0 * PI * i as f64 / segments as f64;
+                Point {
+                    x: center.x + radius * angle.cos(),
+                    y: center.y + radius * angle.sin(),
+                }
+            })
+            .collect()
+    }
+
+    // Intersection de deux polygones convexes
+    pub fn convex_intersection(a: &[Point<f64>], b: &[Point<f64>]) -> Vec<Point<f64>> {
+        Point::clip_polygon(a, b)
+    }
+
+    // Sutherland–Hodgman : `subject` (convexe ou non) est découpé par chaque arête du polygone
+    // convexe `clip`, dans un sens de parcours quelconque. Vide si subject est entièrement dehors.
+    pub fn clip_polygon(subject: &[Point<f64>], clip: &[Point<f64>]) -> Vec<Point<f64>> {
+        if subject.len() < 3 || clip.len() < 3 {
+            return Vec::new();
+        }
+        let winding = Point::signed_polygon_area(clip);
+        let side = |c: &Point<f64>, d: &Point<f64>, p: &Point<f64>| {
+            (d.clone() - c.clone()).cross(&(p.clone() - c.clone())) * winding.signum()
+        };
+
+        let mut output = subject.to_vec();
+        for (c, d) in Point::polygon_edges(clip) {
+            if output.is_empty() {
+                break;
+            }
+            let edge = d.clone() - c.clone();
+            let input = std::mem::take(&mut output);
+            for j in 0..input.len() {
+                let current = &input[j];
+                let previous = &input[(j + input.len() - 1) % input.len()];
+                let current_inside = side(c, d, current) >= 0.0;
+                let previous_inside = side(c, d, previous) >= 0.0;
+                if current_inside != previous_inside {
+                    let direction = current.clone() - previous.clone();
+                    let t = edge.cross(&(c.clone() - previous.clone())) / edge.cross(&direction);
+                    output.push(previous.clone() + direction * t);
+                }
+                if current_inside {
+                    output.push(current.clone());
+                }
+            }
+        }
+        output
+    }
+
+    // Théorème de l'axe séparateur : plus petit vecteur qui, appliqué à `b`, le sépare du
+    // polygone convexe `a`. None si les deux polygones sont déjà disjoints.
+    pub fn minimum_translation_vector(a: &[Point<f64>], b: &[Point<f64>]) -> Option<Point<f64>> {
+        let project = |polygon: &[Point<f64>], axis: &Point<f64>| {
+            polygon.iter().map(|p| p.dot(axis)).fold((f64::INFINITY, f64::NEG_INFINITY), |(lo, hi), v| {
+                (lo.min(v), hi.max(v))
+            })
+        };
+
+        let mut best: Option<(f64, Point<f64>)> = None;
+        for polygon in [a, b] {
+            for (c, d) in Point::polygon_edges(polygon) {
+                let edge = d.clone() - c.clone();
+                let axis = Point { x: -edge.y, y: edge.x }.normalize();
+                let (min_a, max_a) = project(a, &axis);
+                let (min_b, max_b) = project(b, &axis);
+                let overlap = max_a.min(max_b) - min_a.max(min_b);
+                if overlap <= 0.0 {
+                    return None;
+                }
+                if best.as_ref().is_none_or(|(depth, _)| overlap < *depth) {
+                    best = Some((overlap, axis));
+                }
+            }
+        }
+
+        let (depth, mut axis) = best?;
+        let offset = Point::vertex_average(b) - Point::vertex_average(a);
+        if offset.dot(&axis) < 0.0 {
+            axis = -axis;
+        }
+        Some(axis * depth)
+    }
+
+    // Distance au bord du polygone : négative à l'intérieur, positive à l'extérieur
+    pub fn signed_distance_to_polygon(point: &Point<f64>, polygon: &[Point<f64>]) -> f64 {
+        let best = Point::polygon_edges(polygon)
+            .map(|(a, b)| point.distance_to_segment(a, b))
+            .fold(f64::INFINITY, f64::min);
+        if Point::is_inside_polygon(point, polygon) { -best } else { best }
+    }
+
+    // Enveloppe convexe (chaîne monotone d'Andrew), sens trigonométrique, sans répéter le
+    // premier point. Les points alignés sur les arêtes de l'enveloppe sont écartés.
+    pub fn convex_hull(points: &[Point<f64>]) -> Vec<Point<f64>> {
+        let mut sorted = points.to_vec();
+        sorted.sort_by(|a, b| a.x.total_cmp(&b.x).then(a.y.total_cmp(&b.y)));
+        sorted.dedup_by(|a, b| a.x == b.x && a.y == b.y);
+        if sorted.len() < 3 {
+            return sorted;
+        }
+
+        let mut hull: Vec<Point<f64>> = Vec::with_capacity(sorted.len() * 2);
+        for pass in [sorted.clone(), sorted.into_iter().rev().collect()] {
+            let start = hull.len();
+            for p in pass {
+                while hull.len() >= start + 2
+                    && Point::orientation(&hull[hull.len() - 2], &hull[hull.len() - 1], &p) <= 0.0
+                {
+                    hull.pop();
+                }
+                hull.push(p);
+            }
+            hull.pop();
+        }
+        hull
+    }
+
+    // Parcours de Graham, sens trigonométrique en partant du pivot (plus petit y, puis plus petit
+    // x). Avec keep_collinear, les points situés exactement sur une arête de l'enveloppe sont
+    // gardés ; sinon seuls les sommets le sont.
+    pub fn graham_scan(points: &[Point<f64>], keep_collinear: bool) -> Vec<Point<f64>> {
+        let mut sorted = points.to_vec();
+        sorted.sort_by(|a, b| a.y.total_cmp(&b.y).then(a.x.total_cmp(&b.x)));
+        sorted.dedup_by(|a, b| a.x == b.x && a.y == b.y);
+        if sorted.len() < 3 {
+            return sorted;
+        }
+
+        let pivot = sorted[0].clone();
+        let squared_distance = |p: &Point<f64>| (p.clone() - pivot.clone()).dot(&(p.clone() - pivot.clone()));
+        sorted[1..].sort_by(|a, b| {
+            let turn = Point::orientation(&pivot, a, b);
+            if turn > 0.0 {
+                std::cmp::Ordering::Less
+            } else if turn < 0.0 {
+                std::cmp::Ordering::Greater
+            } else {
+                squared_distance(a).total_cmp(&squared_distance(b))
+            }
+        });
+
+        // Les points alignés sur la dernière arête (retour au pivot) doivent être parcourus du
+        // plus loin au plus proche, sauf si tous les points sont alignés
+        if keep_collinear {
+            let last = sorted[sorted.len() - 1].clone();
+            let mut start = sorted.len() - 1;
+            while start > 1 && Point::orientation(&pivot, &sorted[start - 1], &last) == 0.0 {
+                start -= 1;
+            }
+            if start > 1 {
+                sorted[start..].reverse();
+            }
+        }
+
+        let mut hull: Vec<Point<f64>> = Vec::with_capacity(sorted.len());
+        for p in sorted {
+            while hull.len() >= 2 {
+                let turn = Point::orientation(&hull[hull.len() - 2], &hull[hull.len() - 1], &p);
+                if turn < 0.0 || (turn == 0.0 && !keep_collinear) {
+                    hull.pop();
+                } else {
+                    break;
+                }
+            }
+            hull.push(p);
+        }
+        hull
+    }
+
+    // Cercle (centre, rayon) passant par les trois sommets, None s'ils sont alignés
+    pub fn circumcircle(a: &Point<f64>, b: &Point<f64>, c: &Point<f64>) -> Option<(Point<f64>, f64)> {
+        if Point::is_collinear(a, b, c) {
+            return None;
+        }
+        let (ab, ac) = (b.clone() - a.clone(), c.clone() - a.clone());
+        let d = 2.0 * ab.cross(&ac);
+        let (ab2, ac2) = (ab.dot(&ab), ac.dot(&ac));
+        let offset = Point { x: (ac.y * ab2 - ab.y * ac2) / d, y: (ab.x * ac2 - ac.x * ab2) / d };
+        let radius = offset.norm();
+        Some((a.clone() + offset, radius))
+    }
+
+    // Plus petit cercle (centre, rayon) contenant tous les points : version itérative de
+    // l'algorithme de Welzl. Les points sont parcourus dans l'ordre, sans mélange aléatoire.
+    pub fn min_enclosing_circle(points: &[Point<f64>]) -> Option<(Point<f64>, f64)> {
+        let eps = 1e-9;
+        let contains = |circle: &(Point<f64>, f64), p: &Point<f64>| circle.0.distance(p) <= circle.1 + eps;
+        let from_two = |a: &Point<f64>, b: &Point<f64>| (a.midpoint(b), a.distance(b) / 2.0);
+        let from_three = |a: &Point<f64>, b: &Point<f64>, c: &Point<f64>| {
+            Point::circumcircle(a, b, c).unwrap_or_else(|| {
+                // Points alignés : le cercle passe par les deux plus éloignés
+                let candidates = [from_two(a, b), from_two(a, c), from_two(b, c)];
+                candidates.into_iter().max_by(|x, y| x.1.total_cmp(&y.1)).unwrap()
+            })
+        };
+
+        let mut circle = (points.first()?.clone(), 0.0);
+        for i in 1..points.len() {
+            if contains(&circle, &points[i]) {
+                continue;
+            }
+            circle = (points[i].clone(), 0.0);
+            for j in 0..i {
+                if contains(&circle, &points[j]) {
+                    continue;
+                }
+                circle = from_two(&points[i], &points[j]);
+                for k in 0..j {
+                    if !contains(&circle, &points[k]) {
+                        circle = from_three(&points[i], &points[j], &points[k]);
+                    }
+                }
+            }
+        }
+        Some(circle)
+    }
+
+    // (indice, indice, distance) des deux points les plus éloignés. Pied à coulisse tournant
+    // sur l'enveloppe convexe, recherche exhaustive pour les petits ensembles.
+    pub fn farthest_pair(points: &[Point<f64>]) -> Option<(usize, usize, f64)> {
+        if points.len() < 2 {
+            return None;
+        }
+        let mut best = (0, 1, points[0].distance(&points[1]));
+        if points.len() <= 8 {
+            for i in 0..points.len() {
+                for j in i + 1..points.len() {
+                    let d = points[i].distance(&points[j]);
+                    if d > best.2 {
+                        best = (i, j, d);
+                    }
+                }
+            }
+            return Some(best);
+        }
+
+        let hull = Point::convex_hull(points);
+        let index_of = |q: &Point<f64>| points.iter().position(|p| p.x == q.x && p.y == q.y).unwrap();
+        let m = hull.len();
+        if m < 3 {
+            let (i, j) = (index_of(&hull[0]), index_of(&hull[m - 1]));
+            return Some((i, j, points[i].distance(&points[j])));
+        }
+
+        let mut best_pair = (0, 0, 0.0);
+        let mut j = 1;
+        for i in 0..m {
+            let next = (i + 1) % m;
+            while Point::orientation(&hull[i], &hull[next], &hull[(j + 1) % m]).abs()
+                > Point::orientation(&hull[i], &hull[next], &hull[j]).abs()
+            {
+                j = (j + 1) % m;
+            }
+            for k in [i, next] {
+                let d = hull[k].distance(&hull[j]);
+                if d > best_pair.2 {
+                    best_pair = (k, j, d);
+                }
+            }
+        }
+        Some((index_of(&hull[best_pair.0]), index_of(&hull[best_pair.1]), best_pair.2))
+    }
+
+    // Squelette droit d'un polygone convexe : toutes les arêtes avancent vers l'intérieur à
+    // la même vitesse et chaque sommet suit la bissectrice de ses deux arêtes. Quand une arête
+    // s'annule, ses deux sommets fusionnent. Le cas non convexe n'est pas géré.
+    pub fn straight_skeleton_convex(polygon: &[Point<f64>]) -> Vec<(Point<f64>, Point<f64>)> {
+        const EPS: f64 = 1e-9;
+        if polygon.len() < 3 {
+            return Vec::new();
+        }
+        let mut ring = polygon.to_vec();
+        let n = ring.len();
+        if Point::signed_polygon_area(&ring) < 0.0 {
+            ring.reverse();
+        }
+        let normals: Vec<Point<f64>> = (0..n)
+            .map(|i| {
+                let d = (ring[(i + 1) % n].clone() - ring[i].clone()).normalize();
+                Point { x: -d.y, y: d.x }
+            })
+            .collect();
+
+        // (position courante, position de départ, arête qui précède le sommet)
+        let mut vertices: Vec<(Point<f64>, Point<f64>, usize)> =
+            (0..n).map(|i| (ring[i].clone(), ring[i].clone(), (i + n - 1) % n)).collect();
+        let mut skeleton = Vec::new();
+
+        loop {
+            let m = vertices.len();
+            let positions: Vec<Point<f64>> = vertices.iter().map(|v| v.0.clone()).collect();
+            if m < 3 || Point::polygon_area(&positions) < EPS {
+                break;
+            }
+
+            let mut velocities = Vec::with_capacity(m);
+            for k in 0..m {
+                let n1 = &normals[vertices[k].2];
+                let n2 = &normals[vertices[(k + 1) % m].2];
+                let denom = 1.0 + n1.dot(n2);
+                if denom < EPS {
+                    break;
+                }
+                velocities.push((n1.clone() + n2.clone()) * (1.0 / denom));
+            }
+            if velocities.len() < m {
+                break;
+            }
+
+            let mut dt = f64::INFINITY;
+            for k in 0..m {
+                let next = (k + 1) % m;
+                let edge = positions[next].clone() - positions[k].clone();
+                let length = edge.norm();
+                let closing = (velocities[k].clone() - velocities[next].clone()).dot(&edge) / length;
+                if closing > EPS {
+                    dt = dt.min(length / closing);
+                }
+            }
+            if !dt.is_finite() {
+                break;
+            }
+            for (vertex, velocity) in vertices.iter_mut().zip(&velocities) {
+                vertex.0 = vertex.0.clone() + velocity.clone() * dt;
+            }
+
+            let start = (0..m).find(|&k| vertices[(k + m - 1) % m].0.distance(&vertices[k].0) > EPS);
+            let Some(start) = start else {
+                // Tous les sommets se rejoignent en un seul point
+                for (position, origin, _) in vertices.drain(..) {
+                    skeleton.push((origin, position));
+                }
+                return skeleton;
+            };
+            vertices.rotate_left(start);
+
+            let mut merged: Vec<(Point<f64>, Point<f64>, usize)> = Vec::new();
+            let mut k = 0;
+            while k < m {
+                let mut end = k + 1;
+                while end < m && vertices[end].0.distance(&vertices[k].0) <= EPS {
+                    end += 1;
+                }
+                if end - k == 1 {
+                    merged.push(vertices[k].clone());
+                } else {
+                    for (position, origin, _) in &vertices[k..end] {
+                        skeleton.push((origin.clone(), position.clone()));
+                    }
+                    let position = vertices[k].0.clone();
+                    merged.push((position.clone(), position, vertices[k].2));
+                }
+                k = end;
+            }
+            vertices = merged;
+        }
+
+        for (position, origin, _) in &vertices {
+            if origin.distance(position) > EPS {
+                skeleton.push((origin.clone(), position.clone()));
+            }
+        }
+        // Les sommets restants sont alignés : on les relie le long de leur droite
+        let mut rest: Vec<Point<f64>> = Vec::new();
+        for (position, _, _) in vertices {
+            if rest.iter().all(|p| p.distance(&position) > EPS) {
+                rest.push(position);
+            }
+        }
+        if rest.len() >= 2 {
+            let axis = rest[1].clone() - rest[0].clone();
+            rest.sort_by(|a, b| a.dot(&axis).total_cmp(&b.dot(&axis)));
+            for pair in rest.windows(2) {
+                skeleton.push((pair[0].clone(), pair[1].clone()));
+            }
+        }
+        skeleton
+    }
+}
+
+impl Point<i32> {
+    // Cases de la grille traversées par le segment [a, b], extrémités comprises, dans l'ordre de a
+    // vers b. Deux cases successives se touchent par un côté ou un coin.
+    pub fn bresenham_line(a: &Point<i32>, b: &Point<i32>) -> Vec<Point<i32>> {
+        let dx = (b.x - a.x).abs();
+        let dy = -(b.y - a.y).abs();
+        let step_x = if a.x < b.x { 1 } else { -1 };
+        let step_y = if a.y < b.y { 1 } else { -1 };
+        let mut error = dx + dy;
+        let (mut x, mut y) = (a.x, a.y);
+        let mut cells = Vec::with_capacity(dx.max(-dy) as usize + 1);
+        loop {
+            cells.push(Point { x, y });
+            if x == b.x && y == b.y {
+                return cells;
+            }
+            let doubled = 2 * error;
+            if doubled >= dy {
+                error += dy;
+                x += step_x;
+            }
+            if doubled <= dx {
+                error += dx;
+                y += step_y;
+            }
+        }
+    }
+}
+
+// Boîte englobante alignée sur les axes. Les bords font partie de la boîte : un point sur un
+// bord est contenu, et deux boîtes qui ne font que se toucher s'intersectent.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Aabb {
+    pub min: Point<f64>,
+    pub max: Point<f64>,
+}
+
+impl Aabb {
+    pub fn from_points(points: &[Point<f64>]) -> Option<Aabb> {
+        let (min, max) = Point::bounding_box(points)?;
+        Some(Aabb { min, max })
+    }
+
+    pub fn contains(&self, p: &Point<f64>) -> bool {
+        p.x >= self.min.x && p.x <= self.max.x && p.y >= self.min.y && p.y <= self.max.y
+    }
+
+    pub fn intersects(&self, other: &Aabb) -> bool {
+        self.min.x <= other.max.x
+            && other.min.x <= self.max.x
+            && self.min.y <= other.max.y
+            && other.min.y <= self.max.y
+    }
+}
+
+impl<T: fmt::Display> fmt::Display for Point3<T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "({}, {}, {})", self.x, self.y, self.z)
+    }
+}
+
+impl<T: Add<Output = T>> Add for Point3<T> {
+    type Output = Point3<T>;
+
+    fn add(self, other: Point3<T>) -> Point3<T> {
+        Point3 {
+            x: self.x + other.x,
+            y: self.y + other.y,
+            z: self.z + other.z,
+        }
+    }
+}
+
+impl<T: Sub<Output = T>> Sub for Point3<T> {
+    type Output = Point3<T>;
+
+    fn sub(self, other: Point3<T>) -> Point3<T> {
+        Point3 {
+            x: self.x - other.x,
+            y: self.y - other.y,
+            z: self.z - other.z,
+        }
+    }
+}
+
+impl<T: Mul<Output = T> + Copy> Mul<T> for Point3<T> {
+    type Output = Point3<T>;
+
+    fn mul(self, scalar: T) -> Point3<T> {
+        Point3 {
+            x: self.x * scalar,
+            y: self.y * scalar,
+            z: self.z * scalar,
+        }
+    }
+}
+
+impl<T: Mul<Output = T> + Add<Output = T> + Copy> Point3<T> {
+    pub fn dot(&self, other: &Point3<T>) -> T {
+        self.x * other.x + self.y * other.y + self.z * other.z
+    }
+}
+
+impl<T: Mul<Output = T> + Add<Output = T> + Copy + ToF64> Point3<T> {
+    pub fn norm(&self) -> f64 {
+        let sum: f64 = self.dot(self).to_f64();
+        sum.sqrt()
+    }
+
+    pub fn normalize(&self) -> Point3<f64> {
+        let length = self.norm();
+        Point3 {
+            x: self.x.to_f64() / length,
+            y: self.y.to_f64() / length,
+            z: self.z.to_f64() / length,
+        }
+    }
+}
+
+impl<T: Mul<Output = T> + Sub<Output = T> + Copy + ToF64> Point3<T> {
+    // Contrairement au cas 2D, le produit vectoriel est un vecteur
+    pub fn cross(&self, other: &Point3<T>) -> Point3<f64> {
+        Point3 {
+            x: (self.y * other.z - self.z * other.y).to_f64(),
+            y: (self.z * other.x - self.x * other.z).to_f64(),
+            z: (self.x * other.y - self.y * other.x).to_f64(),
+        }
+    }
+
+    pub fn distance(&self, other: &Point3<T>) -> f64 {
+        let dx: f64 = (self.x - other.x).to_f64();
+        let dy: f64 = (self.y - other.y).to_f64();
+        let dz: f64 = (self.z - other.z).to_f64();
+        (dx * dx + dy * dy + dz * dz).sqrt()
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn pseudo_random_points(count: usize, mut seed: u64) -> Vec<Point<f64>> {
+        let mut next = move || {
+            seed = seed.wrapping_mul(6364136223846793005).wrapping_add(1442695040888963407);
+            (seed >> 11) as f64 / (1u64 << 53) as f64 * 100.0 - 50.0
+        };
+        (0..count).map(|_| Point { x: next(), y: next() }).collect()
+    }
+
+    #[test]
+    fn test_transform() {
+        let v1 = Point { x: 3.0, y: 4.0 };
+        let matrix = [[2.0, 0.0], [0.0, 2.0]];
+        let transformed = v1.transform(matrix);
+        assert_eq!(transformed.x, 6.0);
+        assert_eq!(transformed.y, 8.0);
+    }
+
+    #[test]
+    fn test_is_intersecting() {
+        let a1 = Point { x: 1.0, y: 1.0 };
+        let a2 = Point { x: 4.0, y: 4.0 };
+        let b1 = Point { x: 1.0, y: 4.0 };
+        let b2 = Point { x: 4.0, y: 1.0 };
+        assert!(Point::is_intersecting(&a1, &a2, &b1, &b2));
+    }
+
+    #[test]
+    fn test_is_inside_polygon() {
+        let polygon = vec![
+            Point { x: 0.0, y: 0.0 },
+            Point { x: 5.0, y: 0.0 },
+            Point { x: 5.0, y: 5.0 },
+            Point { x: 0.0, y: 5.0 },
+        ];
+        let inside = Point { x: 3.0, y: 3.0 };
+        let outside = Point { x: 6.0, y: 3.0 };
+        assert!(Point::is_inside_polygon(&inside, &polygon));
+        assert!(!Point::is_inside_polygon(&outside, &polygon));
+
+        for on_edge in [
+            Point { x: 5.0, y: 3.0 },
+            Point { x: 2.0, y: 0.0 },
+            Point { x: 0.0, y: 5.0 },
+            Point { x: 3.0, y: 5.0 },
+            Point { x: 0.0, y: 2.5 },
+        ] {
+            assert!(Point::is_inside_polygon(&on_edge, &polygon));
+        }
+        assert!(!Point::is_inside_polygon(&Point { x: 7.0, y: 5.0 }, &polygon));
+        assert!(!Point::is_inside_polygon(&Point { x: -1.0, y: 0.0 }, &polygon));
+        assert!(!Point::is_inside_polygon(&inside, &[]));
+    }
+
+    #[test]
+    fn test_is_collinear() {
+        let v1 = Point { x: 3.0, y: 4.0 };
+        let v2 = Point { x: 5.0, y: 12.0 };
+        assert!(Point::is_collinear(&v1, &v2, &Point { x: 7.0, y: 20.0 }));
+        assert!(!Point::is_collinear(&v1, &v2, &Point { x: 7.0, y: 19.0 }));
+    }
+
+    #[test]
+    fn test_hausdorff_distance() {
+        let a = vec![
+            Point { x: 0.0, y: 0.0 },
+            Point { x: 2.0, y: 1.0 },
+            Point { x: -1.0, y: 3.0 },
+        ];
+        let b: Vec<Point<f64>> = a.iter().map(|p| p.clone() + Point { x: 0.0, y: 1.5 }).collect();
+        assert_eq!(Point::hausdorff_distance(&a, &a), 0.0);
+        assert!((Point::hausdorff_distance(&a, &b) - 1.5).abs() < 1e-10);
+        assert_eq!(Point::hausdorff_distance(&a, &[]), f64::INFINITY);
+    }
+
+    #[test]
+    fn test_best_alignment_angle() {
+        let shape = vec![
+            Point { x: 0.0, y: 0.0 },
+            Point { x: 4.0, y: 0.0 },
+            Point { x: 5.0, y: 2.0 },
+            Point { x: 1.0, y: 3.0 },
+        ];
+        let offset = Point { x: 10.0, y: -2.0 };
+        let rotated: Vec<Point<f64>> = shape.iter().map(|p| p.rotate(37.0) + offset.clone()).collect();
+        let angle = Point::best_alignment_angle(&shape, &rotated);
+        assert!((angle - 37.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_visibility_polygon() {
+        let viewpoint = Point { x: 0.0, y: 0.0 };
+        assert!(Point::visibility_polygon(&viewpoint, &[]).is_empty());
+
+        let wall = (Point { x: 2.0, y: -1.0 }, Point { x: 2.0, y: 1.0 });
+        let visible = Point::visibility_polygon(&viewpoint, &[wall]);
+        assert!(Point::is_inside_polygon(&Point { x: 1.0, y: 0.0 }, &visible));
+        assert!(Point::is_inside_polygon(&Point { x: -3.0, y: 0.0 }, &visible));
+        assert!(Point::is_inside_polygon(&Point { x: 3.0, y: 3.0 }, &visible));
+        assert!(!Point::is_inside_polygon(&Point { x: 3.0, y: 0.0 }, &visible));
+    }
+
+    #[test]
+    fn test_snap_to_grid() {
+        let points = vec![
+            Point { x: 1.234, y: 5.678 },
+            Point { x: 1.234001, y: 5.678 },
+            Point { x: 2.0, y: 0.0 },
+        ];
+        let snapped = Point::snap_to_grid(&points, 0.01);
+        assert_eq!((snapped[0].x, snapped[0].y), (snapped[1].x, snapped[1].y));
+        assert!((snapped[0].x - 1.23).abs() < 1e-12);
+        assert!((snapped[0].y - 5.68).abs() < 1e-12);
+        assert_eq!(Point::dedup_snapped(&points, 0.01).len(), 2);
+    }
+
+    #[test]
+    fn test_polygon_with_holes_area() {
+        let outer = vec![
+            Point { x: 0.0, y: 0.0 },
+            Point { x: 10.0, y: 0.0 },
+            Point { x: 10.0, y: 10.0 },
+            Point { x: 0.0, y: 10.0 },
+        ];
+        let hole = vec![
+            Point { x: 4.0, y: 4.0 },
+            Point { x: 4.0, y: 6.0 },
+            Point { x: 6.0, y: 6.0 },
+            Point { x: 6.0, y: 4.0 },
+        ];
+        assert_eq!(Point::polygon_with_holes_area(&outer, &[hole]), 96.0);
+    }
+
+    #[test]
+    fn test_circle_to_polygon() {
+        let center = Point { x: 2.0, y: -1.0 };
+        let circle = Point::circle_to_polygon(&center, 1.0, 64);
+        assert_eq!(circle.len(), 64);
+        assert!(circle.iter().all(|p| (p.distance(&center) - 1.0).abs() < 1e-12));
+        assert!((Point::polygon_area(&circle) - PI).abs() < PI * 0.01);
+    }
+
+    #[test]
+    fn test_convex_intersection() {
+        let square = |x: f64, y: f64, size: f64| vec![
+            Point { x, y },
+            Point { x: x + size, y },
+            Point { x: x + size, y: y + size },
+            Point { x, y: y + size },
+        ];
+        let overlap = Point::convex_intersection(&square(0.0, 0.0, 2.0), &square(1.0, 0.5, 2.0));
+        assert_eq!(overlap.len(), 4);
+        assert!((Point::polygon_area(&overlap) - 1.5).abs() < 1e-10);
+        assert!(overlap.iter().all(|p| (1.0..=2.0).contains(&p.x) && (0.5..=2.0).contains(&p.y)));
+
+        assert!(Point::convex_intersection(&square(0.0, 0.0, 1.0), &square(5.0, 5.0, 1.0)).is_empty());
+    }
+
+    #[test]
+    fn test_is_general_position() {
+        let mut points = pseudo_random_points(20, 42);
+        assert!(Point::is_general_position(&points));
+
+        points.push(Point { x: 0.0, y: 0.0 });
+        points.push(Point { x: 1.0, y: 1.0 });
+        points.push(Point { x: 3.0, y: 3.0 });
+        assert!(!Point::is_general_position(&points));
+    }
+
+    #[test]
+    fn test_straight_skeleton_convex() {
+        let center = Point { x: 1.0, y: 2.0 };
+        let hexagon = Point::circle_to_polygon(&center, 3.0, 6);
+        let skeleton = Point::straight_skeleton_convex(&hexagon);
+        assert_eq!(skeleton.len(), 6);
+        assert!(skeleton.iter().all(|(_, end)| end.distance(&center) < 1e-9));
+
+        let rectangle = vec![
+            Point { x: 0.0, y: 0.0 },
+            Point { x: 4.0, y: 0.0 },
+            Point { x: 4.0, y: 2.0 },
+            Point { x: 0.0, y: 2.0 },
+        ];
+        let skeleton = Point::straight_skeleton_convex(&rectangle);
+        assert_eq!(skeleton.len(), 5);
+        let (left, right) = (Point { x: 1.0, y: 1.0 }, Point { x: 3.0, y: 1.0 });
+        assert!(skeleton.iter().any(|(a, b)| {
+            (a.distance(&left) < 1e-9 && b.distance(&right) < 1e-9)
+                || (a.distance(&right) < 1e-9 && b.distance(&left) < 1e-9)
+        }));
+    }
+
+    #[test]
+    fn test_area_delta_on_insert() {
+        let square = vec![
+            Point { x: 0.0, y: 0.0 },
+            Point { x: 2.0, y: 0.0 },
+            Point { x: 2.0, y: 2.0 },
+            Point { x: 0.0, y: 2.0 },
+        ];
+        assert_eq!(Point::area_delta_on_insert(&square, 1, &Point { x: 2.0, y: 1.0 }), 0.0);
+
+        let outward = Point { x: 3.0, y: 1.0 };
+        let delta = Point::area_delta_on_insert(&square, 1, &outward);
+        let mut grown = square.clone();
+        grown.insert(2, outward);
+        assert_eq!(delta, 1.0);
+        assert_eq!(Point::polygon_area(&square) + delta, Point::polygon_area(&grown));
+    }
+
+    #[test]
+    fn test_point3() {
+        let a = Point3 { x: 1, y: 2, z: 2 };
+        let b = Point3 { x: 4, y: 6, z: 2 };
+        assert_eq!(a.norm(), 3.0);
+        assert_eq!(a.distance(&b), 5.0);
+        assert_eq!(a.dot(&b), 20);
+
+        let sum = a.clone() + b.clone();
+        assert_eq!((sum.x, sum.y, sum.z), (5, 8, 4));
+        let scaled = (b - a) * 2;
+        assert_eq!((scaled.x, scaled.y, scaled.z), (6, 8, 0));
+
+        let x = Point3 { x: 1.0, y: 0.0, z: 0.0 };
+        let y = Point3 { x: 0.0, y: 1.0, z: 0.0 };
+        let z = x.cross(&y);
+        assert_eq!((z.x, z.y, z.z), (0.0, 0.0, 1.0));
+
+        let unit = Point3 { x: 0.0, y: 3.0, z: 4.0 }.normalize();
+        assert!((unit.norm() - 1.0).abs() < 1e-12);
+    }
+
+    #[test]
+    fn test_tangents() {
+        let straight: Vec<Point<f64>> = (0..5).map(|i| Point { x: i as f64, y: 2.0 * i as f64 }).collect();
+        let expected = Point { x: 1.0, y: 2.0 }.normalize();
+        let tangents = Point::tangents(&straight);
+        assert_eq!(tangents.len(), straight.len());
+        assert!(tangents.iter().all(|t| t.distance(&expected) < 1e-12));
+
+        let corner = vec![
+            Point { x: 0.0, y: 0.0 },
+            Point { x: 2.0, y: 0.0 },
+            Point { x: 2.0, y: 2.0 },
+        ];
+        let tangents = Point::tangents(&corner);
+        let bisector = Point { x: 1.0, y: 1.0 }.normalize();
+        assert!(tangents[1].distance(&bisector) < 1e-12);
+        assert!(tangents[0].distance(&Point { x: 1.0, y: 0.0 }) < 1e-12);
+        assert!(tangents[2].distance(&Point { x: 0.0, y: 1.0 }) < 1e-12);
+
+        let repeated = vec![
+            Point { x: 0.0, y: 0.0 },
+            Point { x: 1.0, y: 0.0 },
+            Point { x: 1.0, y: 0.0 },
+            Point { x: 1.0, y: 3.0 },
+        ];
+        let tangents = Point::tangents(&repeated);
+        assert!(tangents.iter().all(|t| !t.x.is_nan() && !t.y.is_nan()));
+        assert!(tangents[1].distance(&Point { x: 1.0, y: 0.0 }) < 1e-12);
+        assert!(tangents[2].distance(&Point { x: 0.0, y: 1.0 }) < 1e-12);
+
+        let reversal = vec![
+            Point { x: 0.0, y: 0.0 },
+            Point { x: 2.0, y: 0.0 },
+            Point { x: 0.0, y: 0.0 },
+        ];
+        let tangents = Point::tangents(&reversal);
+        assert!(tangents[1].distance(&Point { x: 1.0, y: 0.0 }) < 1e-12);
+        assert!(tangents[2].distance(&Point { x: -1.0, y: 0.0 }) < 1e-12);
+    }
+
+    #[test]
+    fn test_point_on_segment() {
+        let a = Point { x: 1.0, y: 1.0 };
+        let b = Point { x: 5.0, y: 3.0 };
+        assert!(Point::point_on_segment(&Point { x: 3.0, y: 2.0 }, &a, &b, 1e-9));
+        assert!(!Point::point_on_segment(&Point { x: 7.0, y: 4.0 }, &a, &b, 1e-9));
+        assert!(!Point::point_on_segment(&Point { x: 3.0, y: 2.001 }, &a, &b, 1e-9));
+        assert!(Point::point_on_segment(&Point { x: 3.0, y: 2.001 }, &a, &b, 1e-2));
+    }
+
+    #[test]
+    fn test_polygon_area() {
+        let square = vec![
+            Point { x: 0.0, y: 0.0 },
+            Point { x: 1.0, y: 0.0 },
+            Point { x: 1.0, y: 1.0 },
+            Point { x: 0.0, y: 1.0 },
+        ];
+        let triangle = vec![
+            Point { x: 0.0, y: 0.0 },
+            Point { x: 0.0, y: 3.0 },
+            Point { x: 4.0, y: 0.0 },
+        ];
+        assert_eq!(Point::polygon_area(&square), 1.0);
+        assert_eq!(Point::signed_polygon_area(&square), 1.0);
+        assert_eq!(Point::polygon_area(&triangle), 6.0);
+        assert_eq!(Point::signed_polygon_area(&triangle), -6.0);
+        assert_eq!(Point::polygon_area(&triangle[..2]), 0.0);
+        assert_eq!(Point::signed_polygon_area(&[]), 0.0);
+    }
+
+    #[test]
+    fn test_convex_hull() {
+        let points = vec![
+            Point { x: 0.0, y: 0.0 },
+            Point { x: 2.0, y: 0.0 },
+            Point { x: 4.0, y: 0.0 },
+            Point { x: 4.0, y: 4.0 },
+            Point { x: 0.0, y: 4.0 },
+            Point { x: 1.0, y: 2.0 },
+            Point { x: 3.0, y: 1.0 },
+            Point { x: 0.0, y: 2.0 },
+        ];
+        let hull = Point::convex_hull(&points);
+        let corners: Vec<(f64, f64)> = hull.iter().map(|p| (p.x, p.y)).collect();
+        assert_eq!(corners, vec![(0.0, 0.0), (4.0, 0.0), (4.0, 4.0), (0.0, 4.0)]);
+        assert!(Point::signed_polygon_area(&hull) > 0.0);
+
+        assert!(Point::convex_hull(&[]).is_empty());
+        assert_eq!(Point::convex_hull(&points[..2]).len(), 2);
+        let line: Vec<Point<f64>> = (0..5).map(|i| Point { x: i as f64, y: i as f64 }).collect();
+        assert_eq!(Point::convex_hull(&line).len(), 2);
+    }
+
+    #[test]
+    fn test_distance_to_segment() {
+        let a = Point { x: 0.0, y: 0.0 };
+        let b = Point { x: 4.0, y: 0.0 };
+        let above = Point { x: 1.0, y: 3.0 };
+        let beyond = Point { x: 7.0, y: 4.0 };
+        assert_eq!(above.distance_to_segment(&a, &b), 3.0);
+        assert_eq!(beyond.distance_to_segment(&a, &b), 5.0);
+        let foot = above.closest_point_on_segment(&a, &b);
+        assert_eq!((foot.x, foot.y), (1.0, 0.0));
+        assert_eq!(beyond.distance_to_segment(&a, &a), beyond.distance(&a));
+    }
+
+    #[test]
+    fn test_minimum_translation_vector() {
+        let square = |x: f64, y: f64| vec![
+            Point { x, y },
+            Point { x: x + 2.0, y },
+            Point { x: x + 2.0, y: y + 2.0 },
+            Point { x, y: y + 2.0 },
+        ];
+        let mtv = Point::minimum_translation_vector(&square(0.0, 0.0), &square(1.5, 0.5)).unwrap();
+        assert!((mtv.norm() - 0.5).abs() < 1e-12);
+        assert!(mtv.x > 0.0 && mtv.y.abs() < 1e-12);
+
+        let mtv = Point::minimum_translation_vector(&square(0.0, 0.0), &square(-0.5, -1.8)).unwrap();
+        assert!(mtv.x.abs() < 1e-12 && (mtv.y + 0.2).abs() < 1e-12);
+
+        assert!(Point::minimum_translation_vector(&square(0.0, 0.0), &square(3.0, 0.0)).is_none());
+    }
+
+    #[test]
+    fn test_farthest_pair() {
+        for seed in 1..20 {
+            let points = pseudo_random_points(50, seed);
+            let mut brute = 0.0;
+            for i in 0..points.len() {
+                for j in i + 1..points.len() {
+                    brute = f64::max(brute, points[i].distance(&points[j]));
+                }
+            }
+            let (i, j, d) = Point::farthest_pair(&points).unwrap();
+            assert_eq!(d, brute);
+            assert_eq!(points[i].distance(&points[j]), d);
+        }
+
+        let mut points: Vec<Point<f64>> = (0..10).map(|i| Point { x: i as f64 * 0.1, y: 1.0 }).collect();
+        points.push(Point { x: -3.0, y: -3.0 });
+        points.push(Point { x: 4.0, y: 5.0 });
+        let (i, j, d) = Point::farthest_pair(&points).unwrap();
+        assert_eq!((i.min(j), i.max(j)), (10, 11));
+        assert!((d - 113f64.sqrt()).abs() < 1e-12);
+        assert_eq!(Point::farthest_pair(&points[..1]), None);
+    }
+
+    #[test]
+    fn test_offset_polyline() {
+        let segment = vec![Point { x: 0.0, y: 0.0 }, Point { x: 4.0, y: 0.0 }];
+        let offset = Point::offset_polyline(&segment, 1.5);
+        assert_eq!((offset[0].x, offset[0].y), (0.0, 1.5));
+        assert_eq!((offset[1].x, offset[1].y), (4.0, 1.5));
+
+        let corner = vec![
+            Point { x: 0.0, y: 0.0 },
+            Point { x: 2.0, y: 0.0 },
+            Point { x: 2.0, y: 2.0 },
+        ];
+        let expected = [(0.0, 1.0), (1.0, 1.0), (1.0, 2.0)];
+        for (p, (x, y)) in Point::offset_polyline(&corner, 1.0).iter().zip(expected) {
+            assert!(p.distance(&Point { x, y }) < 1e-12);
+        }
+        let outer = Point::offset_polyline(&corner, -1.0);
+        assert!(outer[1].distance(&Point { x: 3.0, y: -1.0 }) < 1e-12);
+    }
+
+    #[test]
+    fn test_approx_eq() {
+        let p = Point { x: 3.0, y: -4.0 };
+        assert_eq!(p.clone(), Point { x: 3.0, y: -4.0 });
+        assert_ne!(Point { x: 1, y: 2 }, Point { x: 2, y: 1 });
+
+        let turned = p.rotate(360.0);
+        assert!(turned.approx_eq(&p, 1e-9));
+        assert!(p.rotate(90.0).rotate(-90.0).approx_eq(&p, 1e-9));
+        assert!(!p.approx_eq(&Point { x: 3.0, y: -3.9 }, 1e-9));
+        assert!(p.normalize().approx_eq(&Point { x: 0.6, y: -0.8 }, 1e-12));
+    }
+
+    #[test]
+    fn test_rotate_around() {
+        let station = Point { x: 2.0, y: 1.0 };
+        let plane = Point { x: 4.0, y: 1.0 };
+        assert!(plane.rotate_around(&station, 90.0).approx_eq(&Point { x: 2.0, y: 3.0 }, 1e-12));
+        assert!(plane.rotate_around(&station, -90.0).approx_eq(&Point { x: 2.0, y: -1.0 }, 1e-12));
+        assert!(station.rotate_around(&station, 45.0).approx_eq(&station, 1e-12));
+
+        let origin = Point { x: 0.0, y: 0.0 };
+        assert!(plane.rotate_around(&origin, 30.0).approx_eq(&plane.rotate(30.0), 1e-12));
+    }
+
+    #[test]
+    fn test_tuple_conversion() {
+        let p = Point::from((3.0, 4.0));
+        assert_eq!(p, Point { x: 3.0, y: 4.0 });
+        assert_eq!(p.norm(), 5.0);
+        let (x, y): (f64, f64) = p.into();
+        assert_eq!((x, y), (3.0, 4.0));
+
+        let q: Point<i32> = (-2, 7).into();
+        assert_eq!(q, Point { x: -2, y: 7 });
+        assert_eq!(<(i32, i32)>::from(q), (-2, 7));
+    }
+
+    #[test]
+    fn test_multi_polygon_centroid() {
+        let square = |x: f64, y: f64, side: f64| {
+            vec![
+                Point { x, y },
+                Point { x: x + side, y },
+                Point { x: x + side, y: y + side },
+                Point { x, y: y + side },
+            ]
+        };
+        let pair = vec![square(0.0, 0.0, 2.0), square(4.0, 2.0, 2.0)];
+        let centroid = Point::multi_polygon_centroid(&pair).unwrap();
+        assert!(centroid.approx_eq(&Point { x: 3.0, y: 2.0 }, 1e-12));
+
+        let lopsided = vec![square(0.0, 0.0, 10.0), square(20.0, 0.0, 0.1)];
+        let centroid = Point::multi_polygon_centroid(&lopsided).unwrap();
+        assert!(centroid.x > 5.0 && centroid.x < 5.01);
+
+        let degenerate = vec![Point { x: 0.0, y: 0.0 }, Point { x: 1.0, y: 1.0 }, Point { x: 2.0, y: 2.0 }];
+        let with_line = vec![square(0.0, 0.0, 2.0), degenerate.clone()];
+        assert!(Point::multi_polygon_centroid(&with_line).unwrap().approx_eq(&Point { x: 1.0, y: 1.0 }, 1e-12));
+        assert!(Point::multi_polygon_centroid(&[degenerate]).is_none());
+        assert!(Point::multi_polygon_centroid(&[]).is_none());
+    }
+
+    #[test]
+    fn test_bounding_box() {
+        let points = vec![
+            Point { x: 1.0, y: -2.0 },
+            Point { x: -3.0, y: 4.0 },
+            Point { x: 2.5, y: 0.0 },
+        ];
+        let (min, max) = Point::bounding_box(&points).unwrap();
+        assert_eq!(min, Point { x: -3.0, y: -2.0 });
+        assert_eq!(max, Point { x: 2.5, y: 4.0 });
+
+        let single = vec![Point { x: 7.0, y: 8.0 }];
+        let (min, max) = Point::bounding_box(&single).unwrap();
+        assert_eq!(min, max);
+        assert_eq!(min, single[0]);
+
+        assert!(Point::bounding_box(&[]).is_none());
+    }
+
+    #[test]
+    fn test_polygon_centroid() {
+        let square = vec![
+            Point { x: 1.0, y: 1.0 },
+            Point { x: 3.0, y: 1.0 },
+            Point { x: 3.0, y: 3.0 },
+            Point { x: 1.0, y: 3.0 },
+        ];
+        let centroid = Point::polygon_centroid(&square).unwrap();
+        assert!(centroid.approx_eq(&Point::vertex_average(&square), 1e-12));
+
+        let l_shape = vec![
+            Point { x: 0.0, y: 0.0 },
+            Point { x: 2.0, y: 0.0 },
+            Point { x: 2.0, y: 1.0 },
+            Point { x: 1.0, y: 1.0 },
+            Point { x: 1.0, y: 2.0 },
+            Point { x: 0.0, y: 2.0 },
+        ];
+        let centroid = Point::polygon_centroid(&l_shape).unwrap();
+        assert!(centroid.approx_eq(&Point { x: 5.0 / 6.0, y: 5.0 / 6.0 }, 1e-12));
+        assert!(!centroid.approx_eq(&Point::vertex_average(&l_shape), 0.1));
+
+        let clockwise: Vec<Point<f64>> = l_shape.iter().rev().cloned().collect();
+        assert!(Point::polygon_centroid(&clockwise).unwrap().approx_eq(&centroid, 1e-12));
+
+        assert!(Point::polygon_centroid(&square[..2]).is_none());
+        let collinear = vec![Point { x: 0.0, y: 0.0 }, Point { x: 1.0, y: 0.0 }, Point { x: 2.0, y: 0.0 }];
+        assert!(Point::polygon_centroid(&collinear).is_none());
+    }
+
+    #[test]
+    fn test_signed_angle_with() {
+        let east = Point { x: 1.0, y: 0.0 };
+        let north = Point { x: 0.0, y: 2.0 };
+        assert!((east.signed_angle_with(&north) - 90.0).abs() < 1e-12);
+        assert!((north.signed_angle_with(&east) + 90.0).abs() < 1e-12);
+        assert_eq!(east.signed_angle_with(&Point { x: -1.0, y: 0.0 }), 180.0);
+        assert_eq!(east.signed_angle_with(&Point { x: -1.0, y: -0.0 }), 180.0);
+
+        let other = Point { x: 1.0, y: -3.0 };
+        let a = Point { x: -2.0, y: 0.5 };
+        assert!((a.signed_angle_with(&other).abs() - a.angle_with(&other)).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_distance_metrics() {
+        let a = Point { x: 1, y: 2 };
+        let b = Point { x: 4, y: -2 };
+        assert_eq!(a.distance(&b), 5.0);
+        assert_eq!(a.manhattan_distance(&b), 7.0);
+        assert_eq!(a.chebyshev_distance(&b), 4.0);
+
+        let c = Point { x: -1.5, y: 0.0 };
+        let d = Point { x: 0.5, y: 2.0 };
+        assert!(c.chebyshev_distance(&d) <= c.distance(&d));
+        assert!(c.distance(&d) <= c.manhattan_distance(&d));
+        assert_eq!(c.manhattan_distance(&d), 4.0);
+        assert_eq!(c.chebyshev_distance(&d), 2.0);
+    }
+
+    #[test]
+    fn test_simplify() {
+        let noisy: Vec<Point<f64>> = (0..=100)
+            .map(|i| Point { x: i as f64, y: if i % 2 == 0 { 0.01 } else { -0.01 } })
+            .collect();
+        let line = Point::simplify(&noisy, 0.1);
+        assert_eq!(line, vec![noisy[0].clone(), noisy[100].clone()]);
+
+        let mut corner: Vec<Point<f64>> = (0..10).map(|i| Point { x: i as f64, y: 0.0 }).collect();
+        corner.extend((0..=10).map(|i| Point { x: 10.0, y: i as f64 }));
+        let simplified = Point::simplify(&corner, 0.5);
+        assert_eq!(
+            simplified,
+            vec![Point { x: 0.0, y: 0.0 }, Point { x: 10.0, y: 0.0 }, Point { x: 10.0, y: 10.0 }]
+        );
+
+        let pair = vec![Point { x: 0.0, y: 0.0 }, Point { x: 1.0, y: 1.0 }];
+        assert_eq!(Point::simplify(&pair, 1.0), pair);
+        assert!(Point::simplify(&[], 1.0).is_empty());
+    }
+
+    #[test]
+    fn test_lerp() {
+        let a = Point { x: 1.0, y: -2.0 };
+        let b = Point { x: 5.0, y: 6.0 };
+        assert_eq!(a.lerp(&b, 0.0), a);
+        assert_eq!(a.lerp(&b, 0.5), Point { x: 3.0, y: 2.0 });
+        assert_eq!(a.lerp(&b, 1.0), b);
+        assert_eq!(a.lerp(&b, 1.5), Point { x: 7.0, y: 10.0 });
+        assert_eq!(a.lerp(&b, -0.5), Point { x: -1.0, y: -6.0 });
+        assert_eq!(a.midpoint(&b), a.lerp(&b, 0.5));
+    }
+
+    #[test]
+    fn test_cubic_bezier() {
+        let p0 = Point { x: 0.0, y: 0.0 };
+        let p1 = Point { x: 0.0, y: 4.0 };
+        let p2 = Point { x: 4.0, y: 4.0 };
+        let p3 = Point { x: 4.0, y: 0.0 };
+        assert_eq!(Point::cubic_bezier(&p0, &p1, &p2, &p3, 0.0), p0);
+        assert_eq!(Point::cubic_bezier(&p0, &p1, &p2, &p3, 1.0), p3);
+        assert_eq!(Point::cubic_bezier(&p0, &p1, &p2, &p3, 0.5), Point { x: 2.0, y: 3.0 });
+
+        let line = Point::cubic_bezier(&p0, &p0.lerp(&p3, 1.0 / 3.0), &p0.lerp(&p3, 2.0 / 3.0), &p3, 0.25);
+        assert!(line.approx_eq(&p0.lerp(&p3, 0.25), 1e-12));
+
+        let samples = Point::sample_bezier(&p0, &p1, &p2, &p3, 8);
+        assert_eq!(samples.len(), 9);
+        assert_eq!(samples[0], p0);
+        assert_eq!(samples[8], p3);
+        assert_eq!(samples[4], Point { x: 2.0, y: 3.0 });
+    }
+
+    #[test]
+    fn test_min_enclosing_circle() {
+        assert!(Point::min_enclosing_circle(&[]).is_none());
+        let single = vec![Point { x: 2.0, y: -1.0 }];
+        assert_eq!(Point::min_enclosing_circle(&single), Some((single[0].clone(), 0.0)));
+
+        let square = vec![
+            Point { x: 0.0, y: 0.0 },
+            Point { x: 2.0, y: 0.0 },
+            Point { x: 2.0, y: 2.0 },
+            Point { x: 0.0, y: 2.0 },
+            Point { x: 1.0, y: 1.5 },
+        ];
+        let (center, radius) = Point::min_enclosing_circle(&square).unwrap();
+        assert!(center.approx_eq(&Point { x: 1.0, y: 1.0 }, 1e-9));
+        assert!((radius - 2.0f64.sqrt()).abs() < 1e-9);
+
+        let triangle = vec![Point { x: 0.0, y: 0.0 }, Point { x: 4.0, y: 0.0 }, Point { x: 2.0, y: 1.0 }];
+        let (center, radius) = Point::min_enclosing_circle(&triangle).unwrap();
+        assert!(center.approx_eq(&Point { x: 2.0, y: 0.0 }, 1e-9));
+        assert!((radius - 2.0).abs() < 1e-9);
+
+        let cloud = pseudo_random_points(300, 11);
+        let (center, radius) = Point::min_enclosing_circle(&cloud).unwrap();
+        assert!(cloud.iter().all(|p| center.distance(p) <= radius + 1e-9));
+        let on_boundary = cloud.iter().filter(|p| (center.distance(p) - radius).abs() < 1e-9).count();
+        assert!(on_boundary >= 2);
+    }
+
+    #[test]
+    fn test_polygon_perimeter() {
+        let rectangle = vec![
+            Point { x: 0.0, y: 0.0 },
+            Point { x: 4.0, y: 0.0 },
+            Point { x: 4.0, y: 3.0 },
+            Point { x: 0.0, y: 3.0 },
+        ];
+        assert_eq!(Point::polygon_perimeter(&rectangle), 14.0);
+        assert_eq!(Point::polygon_perimeter(&[rectangle[0].clone(), rectangle[2].clone()]), 10.0);
+        assert_eq!(Point::polygon_perimeter(&rectangle[..1]), 0.0);
+        assert_eq!(Point::polygon_perimeter(&[]), 0.0);
+    }
+
+    #[test]
+    fn test_clip_polygon() {
+        let window = vec![
+            Point { x: 0.0, y: 0.0 },
+            Point { x: 4.0, y: 0.0 },
+            Point { x: 4.0, y: 4.0 },
+            Point { x: 0.0, y: 4.0 },
+        ];
+        // L concave qui déborde à droite et en haut de la fenêtre
+        let l_shape = vec![
+            Point { x: 2.0, y: 2.0 },
+            Point { x: 6.0, y: 2.0 },
+            Point { x: 6.0, y: 3.0 },
+            Point { x: 3.0, y: 3.0 },
+            Point { x: 3.0, y: 6.0 },
+            Point { x: 2.0, y: 6.0 },
+        ];
+        let clipped = Point::clip_polygon(&l_shape, &window);
+        assert!((Point::polygon_area(&clipped) - 3.0).abs() < 1e-12);
+        assert!(clipped.iter().all(|p| Point::is_inside_polygon(p, &window)));
+
+        let clockwise: Vec<Point<f64>> = window.iter().rev().cloned().collect();
+        assert!((Point::polygon_area(&Point::clip_polygon(&l_shape, &clockwise)) - 3.0).abs() < 1e-12);
+
+        let inner = vec![Point { x: 1.0, y: 1.0 }, Point { x: 2.0, y: 1.0 }, Point { x: 1.0, y: 2.0 }];
+        assert_eq!(Point::clip_polygon(&inner, &window), inner);
+
+        let outside: Vec<Point<f64>> = inner.iter().map(|p| p.clone() + Point { x: 10.0, y: 0.0 }).collect();
+        assert!(Point::clip_polygon(&outside, &window).is_empty());
+    }
+
+    #[test]
+    fn test_barycentric() {
+        let a = Point { x: 0.0, y: 0.0 };
+        let b = Point { x: 4.0, y: 0.0 };
+        let c = Point { x: 0.0, y: 2.0 };
+        assert_eq!(Point::barycentric(&a, &a, &b, &c), (1.0, 0.0, 0.0));
+        assert_eq!(Point::barycentric(&c, &a, &b, &c), (0.0, 0.0, 1.0));
+
+        let p = Point { x: 1.0, y: 0.5 };
+        let (u, v, w) = Point::barycentric(&p, &a, &b, &c);
+        assert!((u + v + w - 1.0).abs() < 1e-12);
+        let rebuilt = a.clone() * u + b.clone() * v + c.clone() * w;
+        assert!(rebuilt.approx_eq(&p, 1e-12));
+
+        assert!(Point::point_in_triangle(&p, &a, &b, &c));
+        assert!(Point::point_in_triangle(&Point { x: 2.0, y: 1.0 }, &a, &b, &c));
+        assert!(Point::point_in_triangle(&p, &c, &b, &a));
+        assert!(!Point::point_in_triangle(&Point { x: 3.0, y: 1.0 }, &a, &b, &c));
+
+        let flat = Point { x: 8.0, y: 0.0 };
+        let (u, v, w) = Point::barycentric(&p, &a, &b, &flat);
+        assert!(u.is_nan() && v.is_nan() && w.is_nan());
+        assert!(!Point::point_in_triangle(&b, &a, &b, &flat));
+    }
+
+    #[test]
+    fn test_integer_norm() {
+        assert_eq!(Point { x: 3i32, y: 4i32 }.norm(), 5.0);
+        assert_eq!(Point { x: -6i64, y: 8i64 }.norm(), 10.0);
+        assert_eq!(Point { x: 3usize, y: 4usize }.norm(), 5.0);
+        assert!(Point { x: 3i64, y: 4i64 }.normalize().approx_eq(&Point { x: 0.6, y: 0.8 }, 1e-12));
+        assert_eq!(Point { x: 1i64, y: 1i64 }.distance(&Point { x: 4i64, y: 5i64 }), 5.0);
+        assert_eq!(Point { x: 1.5f32, y: 2.0f32 }.norm(), 2.5);
+        assert_eq!(Point3 { x: 2i64, y: 3i64, z: 6i64 }.norm(), 7.0);
+    }
+
+    #[test]
+    fn test_reflect_across_line() {
+        let p = Point { x: 3.0, y: 1.0 };
+        let vertical = (Point { x: 1.0, y: 0.0 }, Point { x: 1.0, y: 5.0 });
+        assert_eq!(p.reflect_across_line(&vertical.0, &vertical.1), Point { x: -1.0, y: 1.0 });
+        let horizontal = (Point { x: -2.0, y: 2.0 }, Point { x: 4.0, y: 2.0 });
+        assert_eq!(p.reflect_across_line(&horizontal.0, &horizontal.1), Point { x: 3.0, y: 3.0 });
+
+        let diagonal = (Point { x: 0.0, y: 0.0 }, Point { x: 2.0, y: 2.0 });
+        assert!(p.reflect_across_line(&diagonal.0, &diagonal.1).approx_eq(&Point { x: 1.0, y: 3.0 }, 1e-12));
+
+        let on_line = Point { x: 5.0, y: 5.0 };
+        assert!(on_line.reflect_across_line(&diagonal.0, &diagonal.1).approx_eq(&on_line, 1e-12));
+        let twice = p.reflect_across_line(&horizontal.0, &vertical.1).reflect_across_line(&horizontal.0, &vertical.1);
+        assert!(twice.approx_eq(&p, 1e-12));
+    }
+
+    #[test]
+    fn test_winding_number() {
+        // Pentagramme tracé en reliant un sommet sur deux : le pentagone central est couvert deux fois
+        let center = Point { x: 0.0, y: 0.0 };
+        let pentagon = Point::circle_to_polygon(&center, 10.0, 5);
+        let star: Vec<Point<f64>> = (0..5).map(|i| pentagon[(i * 2) % 5].clone()).collect();
+        assert_eq!(Point::winding_number(&center, &star).abs(), 2);
+        assert!(!Point::is_inside_polygon(&center, &star));
+        assert!(Point::is_inside_polygon_nonzero(&center, &star));
+
+        let branch = center.lerp(&star[0], 0.9);
+        assert_eq!(Point::winding_number(&branch, &star).abs(), 1);
+        assert!(Point::is_inside_polygon(&branch, &star));
+        assert!(Point::is_inside_polygon_nonzero(&branch, &star));
+
+        let far = Point { x: 20.0, y: 0.0 };
+        assert_eq!(Point::winding_number(&far, &star), 0);
+        assert!(!Point::is_inside_polygon_nonzero(&far, &star));
+
+        let square = vec![
+            Point { x: 0.0, y: 0.0 },
+            Point { x: 2.0, y: 0.0 },
+            Point { x: 2.0, y: 2.0 },
+            Point { x: 0.0, y: 2.0 },
+        ];
+        let inside = Point { x: 1.0, y: 1.0 };
+        assert_eq!(Point::winding_number(&inside, &square), 1);
+        let clockwise: Vec<Point<f64>> = square.iter().rev().cloned().collect();
+        assert_eq!(Point::winding_number(&inside, &clockwise), -1);
+        assert!(Point::is_inside_polygon_nonzero(&Point { x: 2.0, y: 1.0 }, &square));
+    }
+
+    #[test]
+    fn test_transform_affine() {
+        let p = Point { x: 3.0, y: 4.0 };
+        let translation = [[1.0, 0.0, 2.0], [0.0, 1.0, -1.0], [0.0, 0.0, 1.0]];
+        assert_eq!(p.transform_affine(translation), Point { x: 5.0, y: 3.0 });
+
+        let (sin, cos) = (PI / 2.0).sin_cos();
+        let rotate_then_translate = [[cos, -sin, 10.0], [sin, cos, 0.0], [0.0, 0.0, 1.0]];
+        let expected = p.rotate(90.0) + Point { x: 10.0, y: 0.0 };
+        assert!(p.transform_affine(rotate_then_translate).approx_eq(&expected, 1e-12));
+
+        let scale = [[2.0, 0.0, 0.0], [0.0, 2.0, 0.0], [0.0, 0.0, 1.0]];
+        assert_eq!(p.transform_affine(scale), p.transform([[2.0, 0.0], [0.0, 2.0]]));
+
+        let projective = [[1.0, 0.0, 0.0], [0.0, 1.0, 0.0], [0.0, 0.0, 2.0]];
+        assert_eq!(p.transform_affine(projective), Point { x: 1.5, y: 2.0 });
+    }
+
+    #[test]
+    fn test_polygon_edges() {
+        let triangle = vec![Point { x: 0.0, y: 0.0 }, Point { x: 1.0, y: 0.0 }, Point { x: 0.0, y: 1.0 }];
+        let edges: Vec<(&Point<f64>, &Point<f64>)> = Point::polygon_edges(&triangle).collect();
+        assert_eq!(
+            edges,
+            vec![(&triangle[0], &triangle[1]), (&triangle[1], &triangle[2]), (&triangle[2], &triangle[0])]
+        );
+        assert_eq!(Point::polygon_edges(&triangle[..2]).count(), 2);
+        assert_eq!(Point::polygon_edges(&triangle[..1]).count(), 0);
+        assert_eq!(Point::polygon_edges(&[]).count(), 0);
+    }
+
+    #[test]
+    fn test_graham_scan() {
+        let mut points = Vec::new();
+        for x in 0..=2 {
+            for y in 0..=2 {
+                points.push(Point { x: x as f64, y: y as f64 });
+            }
+        }
+        points.reverse();
+
+        let corners = Point::graham_scan(&points, false);
+        assert_eq!(
+            corners,
+            vec![
+                Point { x: 0.0, y: 0.0 },
+                Point { x: 2.0, y: 0.0 },
+                Point { x: 2.0, y: 2.0 },
+                Point { x: 0.0, y: 2.0 },
+            ]
+        );
+        let mut andrew = Point::convex_hull(&points);
+        andrew.sort_by(|a, b| a.y.total_cmp(&b.y).then(a.x.total_cmp(&b.x)));
+        let mut sorted_corners = corners.clone();
+        sorted_corners.sort_by(|a, b| a.y.total_cmp(&b.y).then(a.x.total_cmp(&b.x)));
+        assert_eq!(sorted_corners, andrew);
+
+        let boundary = Point::graham_scan(&points, true);
+        assert_eq!(
+            boundary,
+            vec![
+                Point { x: 0.0, y: 0.0 },
+                Point { x: 1.0, y: 0.0 },
+                Point { x: 2.0, y: 0.0 },
+                Point { x: 2.0, y: 1.0 },
+                Point { x: 2.0, y: 2.0 },
+                Point { x: 1.0, y: 2.0 },
+                Point { x: 0.0, y: 2.0 },
+                Point { x: 0.0, y: 1.0 },
+            ]
+        );
+
+        let cloud = pseudo_random_points(200, 5);
+        assert_eq!(Point::graham_scan(&cloud, false).len(), Point::convex_hull(&cloud).len());
+        let line = vec![Point { x: 2.0, y: 2.0 }, Point { x: 0.0, y: 0.0 }, Point { x: 1.0, y: 1.0 }];
+        assert_eq!(Point::graham_scan(&line, true).len(), 3);
+        assert_eq!(Point::graham_scan(&line, false), vec![Point { x: 0.0, y: 0.0 }, Point { x: 2.0, y: 2.0 }]);
+    }
+
+    #[test]
+    fn test_reject_from() {
+        let v = Point { x: 3.0, y: 4.0 };
+        let axis = Point { x: 2.0, y: 0.0 };
+        assert_eq!(v.reject_from(&axis), Point { x: 0.0, y: 4.0 });
+
+        let other = Point { x: 1.0, y: -2.5 };
+        let rejection = v.reject_from(&other);
+        assert!(rejection.dot(&other).abs() < 1e-12);
+        assert!((v.project_on(&other) + rejection).approx_eq(&v, 1e-12));
+    }
+
+    #[test]
+    fn test_bresenham_line() {
+        let diagonal = Point::bresenham_line(&Point { x: 0, y: 0 }, &Point { x: 5, y: 5 });
+        assert_eq!(diagonal.len(), 6);
+        assert!(diagonal.iter().all(|p| p.x == p.y));
+
+        let steep = Point::bresenham_line(&Point { x: 1, y: 2 }, &Point { x: -1, y: -7 });
+        assert_eq!(steep.len(), 10);
+        assert_eq!(steep[0], Point { x: 1, y: 2 });
+        assert_eq!(steep[9], Point { x: -1, y: -7 });
+
+        for (a, b) in [((0, 0), (7, -3)), ((4, 4), (-6, 1)), ((-2, 5), (-2, -1)), ((3, 3), (3, 3))] {
+            let (a, b) = (Point::from(a), Point::from(b));
+            let line = Point::bresenham_line(&a, &b);
+            assert_eq!(line.len() as f64, a.chebyshev_distance(&b) + 1.0);
+            assert!(line.windows(2).all(|w| w[0].chebyshev_distance(&w[1]) == 1.0));
+        }
+    }
+
+    #[test]
+    fn test_circumcircle() {
+        let a = Point { x: 1.0, y: 1.0 };
+        let b = Point { x: 7.0, y: 1.0 };
+        let c = Point { x: 1.0, y: 9.0 };
+        let (center, radius) = Point::circumcircle(&a, &b, &c).unwrap();
+        assert!(center.approx_eq(&b.midpoint(&c), 1e-12));
+        assert!((radius - 5.0).abs() < 1e-12);
+        for p in [&a, &b, &c] {
+            assert!((center.distance(p) - radius).abs() < 1e-12);
+        }
+
+        let aligned = Point { x: 4.0, y: 1.0 };
+        assert!(Point::circumcircle(&a, &aligned, &b).is_none());
+    }
+
+    #[test]
+    fn test_aabb() {
+        let square = |x: f64, y: f64| Aabb { min: Point { x, y }, max: Point { x: x + 2.0, y: y + 2.0 } };
+        let points = vec![Point { x: 1.0, y: 3.0 }, Point { x: -1.0, y: 0.5 }, Point { x: 4.0, y: 2.0 }];
+        let b = Aabb::from_points(&points).unwrap();
+        assert_eq!(b, Aabb { min: Point { x: -1.0, y: 0.5 }, max: Point { x: 4.0, y: 3.0 } });
+        assert!(points.iter().all(|p| b.contains(p)));
+        assert!(b.contains(&Point { x: 4.0, y: 0.5 }));
+        assert!(!b.contains(&Point { x: 4.1, y: 1.0 }));
+        assert!(Aabb::from_points(&[]).is_none());
+
+        let a = square(0.0, 0.0);
+        assert!(a.intersects(&square(1.0, 1.0)));
+        assert!(a.intersects(&square(2.0, 0.0)));
+        assert!(a.intersects(&square(2.0, 2.0)));
+        assert!(!a.intersects(&square(2.1, 0.0)));
+        assert!(!a.intersects(&square(0.0, -2.5)));
+    }
+
+    #[test]
+    fn test_catmull_rom() {
+        let waypoints = vec![
+            Point { x: 0.0, y: 0.0 },
+            Point { x: 2.0, y: 3.0 },
+            Point { x: 5.0, y: 1.0 },
+            Point { x: 6.0, y: 4.0 },
+        ];
+        let curve = Point::catmull_rom(&waypoints, 4);
+        assert_eq!(curve.len(), 13);
+        for (i, waypoint) in waypoints.iter().enumerate() {
+            assert!(curve[i * 4].approx_eq(waypoint, 1e-12));
+        }
+
+        let line: Vec<Point<f64>> = (0..4).map(|i| Point { x: i as f64, y: 2.0 * i as f64 }).collect();
+        for p in Point::catmull_rom(&line, 5) {
+            assert!((p.y - 2.0 * p.x).abs() < 1e-12);
+        }
+
+        assert_eq!(Point::catmull_rom(&waypoints[..1], 4), waypoints[..1].to_vec());
+        assert_eq!(Point::catmull_rom(&waypoints[..2], 0).len(), 2);
+    }
+}
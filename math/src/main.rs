@@ -1,273 +1,666 @@
 #![allow(dead_code)]
-use std::ops::{Add, Sub, Mul};
 use std::f64::consts::PI;
-use std::fmt;
+use std::collections::HashMap;
+use math::geometry::Point;
 
-#[derive(Debug, Clone)]
-struct Point<T> {
-    x: T,
-    y: T,
-}
-
-#[derive(Debug)]
+#[derive(Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 struct Plane {
     position: Point<f64>,
     velocity: f64,
     direction: f64, // En radians
 }
 
-#[derive(Debug)]
+#[derive(Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 struct Station {
     position: Point<f64>,
     radius: f64, // Rayon du cercle de signal
 }
 
-impl<T: fmt::Display> fmt::Display for Point<T> {
-    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        write!(f, "({}, {})", self.x, self.y)
+impl Station {
+    // Points d'intersection des deux cercles de couverture : vide s'ils sont disjoints, si l'un
+    // contient l'autre ou s'ils sont confondus, un seul point s'ils sont tangents
+    fn intersection_points(&self, other: &Station) -> Vec<Point<f64>> {
+        let eps = 1e-9;
+        let d = self.position.distance(&other.position);
+        let (r1, r2) = (self.radius, other.radius);
+        if d < eps || d > r1 + r2 + eps || d < (r1 - r2).abs() - eps {
+            return Vec::new();
+        }
+        let direction = (other.position.clone() - self.position.clone()) / d;
+        let a = (d * d + r1 * r1 - r2 * r2) / (2.0 * d);
+        let base = self.position.clone() + direction.clone() * a;
+        let h = (r1 * r1 - a * a).max(0.0).sqrt();
+        if h < eps {
+            return vec![base];
+        }
+        let offset = Point { x: -direction.y, y: direction.x } * h;
+        vec![base.clone() + offset.clone(), base - offset]
+    }
+
+    // Chaque cercle est remplacé par un polygone régulier qui lui est circonscrit, si bien que
+    // l'enveloppe obtenue contient toujours entièrement chaque cercle.
+    fn circles_convex_hull(stations: &[Station], segments_per_arc: usize) -> Vec<Point<f64>> {
+        let segments = segments_per_arc.max(3);
+        let scale = 1.0 / (PI / segments as f64).cos();
+        let samples: Vec<Point<f64>> = stations
+            .iter()
+            .flat_map(|s| Point::circle_to_polygon(&s.position, s.radius * scale, segments))
+            .collect();
+        Point::convex_hull(&samples)
     }
 }
 
-impl<T: Add<Output = T>> Add for Point<T> {
-    type Output = Point<T>;
+/*
+(Selon un exo brilliant)
+rac((x - a)² + (y - b)²) = distance entre deux vecteurs quand dans la même direction
+ */
+/*fn main() -> Result<(), Box<dyn std::error::Error>> {
+    let _planet = Point { x: 0.0, y: 0.0 };
+
+    let spaceship = Point { x: -5.0, y: 6.0 };
+    let asteroid = Point { x: -3.0, y: 4.5 };
+
+    let d = spaceship.distance(&asteroid);
+    println!("Distance entre le vaisseau et l'astéroïde : {:.2}", d);
 
-    fn add(self, other: Point<T>) -> Point<T> {
-        Point {
-            x: self.x + other.x,
-            y: self.y + other.y,
+    let alert_threshold = 3.0;
+    if d < alert_threshold {
+        println!("🚨 Alerte ! L'astéroïde est trop proche du vaisseau !");
+    } else {
+        println!("✅ Pas de danger immédiat.");
+    }
+
+    Ok(())
+}*/
+
+impl Plane {
+    fn move_forward(&mut self) {
+        self.position += self.velocity_vector();
+    }
+
+    fn is_near_station(&self, station: &Station) -> bool {
+        self.signal_strength(station) > 0.0
+    }
+
+    // Station la plus proche de l'avion et sa distance, None s'il n'y a aucune station
+    fn nearest_station<'a>(&self, stations: &'a [Station]) -> Option<(&'a Station, f64)> {
+        stations
+            .iter()
+            .map(|s| (s, self.position.distance(&s.position)))
+            .min_by(|a, b| a.1.total_cmp(&b.1))
+    }
+
+    // Décroissance linéaire : 1.0 au centre de la station, 0.0 à partir de radius
+    fn signal_strength(&self, station: &Station) -> f64 {
+        if station.radius <= 0.0 {
+            return 0.0;
         }
+        let dist = self.position.distance(&station.position);
+        (1.0 - dist / station.radius).max(0.0)
     }
-}
 
-impl<T: Sub<Output = T>> Sub for Point<T> {
-    type Output = Point<T>;
+    fn is_colliding(&self, other: &Plane, collision_radius: f64) -> bool {
+        let dist = ((self.position.x - other.position.x).powi(2) +
+                    (self.position.y - other.position.y).powi(2)).sqrt();
+        dist < collision_radius
+    }
+
+    // Tourne de delta_radians (sens trigonométrique), le cap restant dans [0, 2π)
+    fn turn(&mut self, delta_radians: f64) {
+        let direction = (self.direction + delta_radians).rem_euclid(2.0 * PI);
+        // rem_euclid peut renvoyer 2π par arrondi pour un résultat à peine négatif
+        self.direction = if direction >= 2.0 * PI { 0.0 } else { direction };
+    }
 
-    fn sub(self, other: Point<T>) -> Point<T> {
-        Point {
-            x: self.x - other.x,
-            y: self.y - other.y,
+    // Cap pointé vers target ; inchangé si l'avion est déjà sur la cible
+    fn set_heading_towards(&mut self, target: &Point<f64>) {
+        let offset = target.clone() - self.position.clone();
+        if offset.x == 0.0 && offset.y == 0.0 {
+            return;
         }
+        self.direction = 0.0;
+        self.turn(offset.y.atan2(offset.x));
     }
-}
 
-impl<T: Mul<Output = T> + Copy> Mul<T> for Point<T> {
-    type Output = Point<T>;
+    // Déplacement effectué à chaque appel de move_forward
+    fn velocity_vector(&self) -> Point<f64> {
+        Point::from_polar(self.velocity, self.direction)
+    }
 
-    fn mul(self, scalar: T) -> Point<T> {
-        Point {
-            x: self.x * scalar,
-            y: self.y * scalar,
+    // Premier instant (en pas, continu) où les deux avions passent à moins de `radius` l'un
+    // de l'autre, en supposant des trajectoires rectilignes uniformes. 0.0 s'ils sont déjà
+    // trop proches, None s'ils ne le seront jamais.
+    fn time_to_collision(&self, other: &Plane, radius: f64) -> Option<f64> {
+        let dp = other.position.clone() - self.position.clone();
+        let dv = other.velocity_vector() - self.velocity_vector();
+        let c = dp.dot(&dp) - radius * radius;
+        if c < 0.0 {
+            return Some(0.0);
         }
+        let a = dv.dot(&dv);
+        let b = 2.0 * dp.dot(&dv);
+        let discriminant = b * b - 4.0 * a * c;
+        if a == 0.0 || discriminant < 0.0 {
+            return None;
+        }
+        let t = (-b - discriminant.sqrt()) / (2.0 * a);
+        (t >= 0.0).then_some(t)
     }
-}
 
-impl<T: Mul<Output = T> + Add<Output = T> + Copy> Point<T> {
-    fn dot(&self, other: &Point<T>) -> T {
-        self.x * other.x + self.y * other.y
+    // Comme time_to_collision, limité à l'intervalle [0, t_max]
+    fn collides_within(&self, other: &Plane, radius: f64, t_max: f64) -> Option<f64> {
+        self.time_to_collision(other, radius).filter(|&t| t <= t_max)
     }
-}
 
-impl<T: Mul<Output = T> + Add<Output = T> + Copy + Into<f64>> Point<T> {
-    fn norm(&self) -> f64 {
-        let sum: f64 = (self.x * self.x + self.y * self.y).into();
-        sum.sqrt()
+    // Gisement de l'autre avion par rapport au cap, en degrés dans ]-180, 180] (0 = droit devant)
+    fn relative_bearing(&self, other: &Plane) -> f64 {
+        let heading = Point { x: self.direction.cos(), y: self.direction.sin() };
+        heading.signed_angle_with(&(other.position.clone() - self.position.clone()))
     }
-}
 
-impl<T: Mul<Output = T> + Add<Output = T> + Copy + Into<f64>> Point<T> {
-    fn normalize(&self) -> Point<f64> {
-        let length = self.norm();
-        Point {
-            x: self.x.into() / length,
-            y: self.y.into() / length,
+    // Pour un avion en coordonnées géographiques (x = longitude, y = latitude, en degrés) qui
+    // suit un grand cercle : direction garde la convention de move_forward (0 = est, PI/2 =
+    // nord) et velocity est l'arc parcouru à chaque pas, en degrés. Renvoie, dans l'ordre du
+    // trajet, les points où la route coupe un méridien ou un parallèle entier.
+    fn graticule_crossings(&self, steps: usize) -> Vec<Point<f64>> {
+        const SUBSTEPS: usize = 16;
+        let (lat1, lon1) = (self.position.y.to_radians(), self.position.x.to_radians());
+        let bearing = PI / 2.0 - self.direction;
+        let at = |distance: f64| {
+            let delta = distance.to_radians();
+            let lat2 = (lat1.sin() * delta.cos() + lat1.cos() * delta.sin() * bearing.cos()).asin();
+            let lon2 = lon1 + (bearing.sin() * delta.sin() * lat1.cos())
+                .atan2(delta.cos() - lat1.sin() * lat2.sin());
+            Point { x: lon2.to_degrees(), y: lat2.to_degrees() }
+        };
+        // Longitude ramenée à moins de 180° de `reference` pour éviter les sauts à l'antiméridien
+        let unwrap = |lon: f64, reference: f64| lon - 360.0 * ((lon - reference) / 360.0).round();
+
+        let mut crossings = Vec::new();
+        let total = steps * SUBSTEPS;
+        let ds = self.velocity / SUBSTEPS as f64;
+        let mut previous = at(0.0);
+        for i in 1..=total {
+            let (s0, s1) = ((i - 1) as f64 * ds, i as f64 * ds);
+            let mut current = at(s1);
+            current.x = unwrap(current.x, previous.x);
+
+            let mut found: Vec<(f64, Point<f64>)> = Vec::new();
+            for axis in 0..2 {
+                let value = |p: &Point<f64>| if axis == 0 { p.x } else { p.y };
+                let (a, b) = (value(&previous), value(&current));
+                let (low, high) = (a.min(b).floor() as i64 + 1, a.max(b).floor() as i64);
+                for k in low..=high {
+                    let target = k as f64;
+                    let (mut lo, mut hi) = (s0, s1);
+                    for _ in 0..50 {
+                        let mid = (lo + hi) / 2.0;
+                        let mut p = at(mid);
+                        p.x = unwrap(p.x, previous.x);
+                        if (value(&p) < target) == (a < target) { lo = mid } else { hi = mid }
+                    }
+                    let mut p = at(hi);
+                    p.x = unwrap(p.x, previous.x);
+                    if axis == 0 { p.x = target } else { p.y = target }
+                    found.push((hi, p));
+                }
+            }
+            found.sort_by(|a, b| a.0.total_cmp(&b.0));
+            crossings.extend(found.into_iter().map(|(_, p)| p));
+            previous = current;
         }
+        for p in &mut crossings {
+            p.x = unwrap(p.x, 0.0);
+        }
+        crossings
     }
-}
 
-impl<T: Mul<Output = T> + Sub<Output = T> + Copy> Point<T> {
-    fn cross(&self, other: &Point<T>) -> T {
-        self.x * other.y - self.y * other.x
+    // (distance signée minimale, pas où elle est atteinte), le pas 0 étant la position actuelle
+    fn closest_approach_to_polygon(&self, polygon: &[Point<f64>], steps: usize) -> (f64, f64) {
+        let step = self.velocity_vector();
+        let mut best = (f64::INFINITY, 0.0);
+        for k in 0..=steps {
+            let position = self.position.clone() + step.clone() * k as f64;
+            let dist = Point::signed_distance_to_polygon(&position, polygon);
+            if dist < best.0 {
+                best = (dist, k as f64);
+            }
+        }
+        best
     }
 }
 
-impl Point<f64> {
-    fn rotate(&self, angle: f64) -> Point<f64> {
-        let rad = angle * PI / 180.0;
-        Point {
-            x: self.x * rad.cos() - self.y * rad.sin(),
-            y: self.x * rad.sin() + self.y * rad.cos(),
-        }
-    }
+// Positions des avions rangées par case de côté cell_size, pour ne tester is_colliding que
+// sur les paires voisines au lieu de toutes les paires
+struct SpatialGrid {
+    cell_size: f64,
+    cells: HashMap<(i64, i64), Vec<usize>>,
+    positions: Vec<Point<f64>>,
 }
 
-impl<T: Sub<Output = T> + Mul<Output = T> + Copy + Into<f64>> Point<T> {
-    fn distance(&self, other: &Point<T>) -> f64 {
-        let dx = (self.x - other.x).into();
-        let dy = (self.y - other.y).into();
-        (dx * dx + dy * dy).sqrt()
+impl SpatialGrid {
+    fn new(planes: &[Plane], cell_size: f64) -> SpatialGrid {
+        assert!(cell_size > 0.0, "cell_size must be positive");
+        let mut grid = SpatialGrid { cell_size, cells: HashMap::new(), positions: Vec::new() };
+        grid.rebuild(planes);
+        grid
     }
-}
 
-impl Point<f64> {
-    fn angle_with(&self, other: &Point<f64>) -> f64 {
-        let dot_product = self.dot(other);
-        let norms = self.norm() * other.norm();
-        (dot_product / norms).acos().to_degrees()
+    fn cell_of(&self, p: &Point<f64>) -> (i64, i64) {
+        ((p.x / self.cell_size).floor() as i64, (p.y / self.cell_size).floor() as i64)
     }
 
-    fn project_on(&self, other: &Point<f64>) -> Point<f64> {
-        let scalar = self.dot(other) / other.dot(other);
-        other.clone() * scalar
+    // À appeler à chaque pas de simulation, les cases déjà allouées sont réutilisées
+    fn rebuild(&mut self, planes: &[Plane]) {
+        for bucket in self.cells.values_mut() {
+            bucket.clear();
+        }
+        self.positions.clear();
+        for (i, plane) in planes.iter().enumerate() {
+            let cell = self.cell_of(&plane.position);
+            self.cells.entry(cell).or_default().push(i);
+            self.positions.push(plane.position.clone());
+        }
     }
-    
-    fn transform(&self, matrix: [[f64; 2]; 2]) -> Point<f64> {
-        Point {
-            x: matrix[0][0] * self.x + matrix[0][1] * self.y,
-            y: matrix[1][0] * self.x + matrix[1][1] * self.y,
+
+    // Paires (i, j), i < j, dont les cases sont assez proches pour que les avions soient à
+    // moins de radius l'un de l'autre. C'est un filtre : il faut encore appeler is_colliding.
+    fn potential_collisions(&self, radius: f64) -> Vec<(usize, usize)> {
+        let reach = ((radius / self.cell_size).ceil() as i64).max(1);
+        let mut pairs = Vec::new();
+        for (i, p) in self.positions.iter().enumerate() {
+            let (cx, cy) = self.cell_of(p);
+            for dx in -reach..=reach {
+                for dy in -reach..=reach {
+                    if let Some(bucket) = self.cells.get(&(cx + dx, cy + dy)) {
+                        pairs.extend(bucket.iter().filter(|&&j| j > i).map(|&j| (i, j)));
+                    }
+                }
+            }
         }
+        pairs.sort_unstable();
+        pairs
     }
-    
-    fn is_intersecting(p1: &Point<f64>, p2: &Point<f64>, q1: &Point<f64>, q2: &Point<f64>) -> bool {
-        let cross1 = (q1.x - p1.x) * (p2.y - p1.y) - (q1.y - p1.y) * (p2.x - p1.x);
-        let cross2 = (q2.x - p1.x) * (p2.y - p1.y) - (q2.y - p1.y) * (p2.x - p1.x);
-        let cross3 = (p1.x - q1.x) * (q2.y - q1.y) - (p1.y - q1.y) * (q2.x - q1.x);
-        let cross4 = (p2.x - q1.x) * (q2.y - q1.y) - (p2.y - q1.y) * (q2.x - q1.x);
+}
+
+// Ce qui s'est passé pendant un pas de simulation, les indices sont ceux de `planes` et `stations`
+#[derive(Debug, PartialEq)]
+enum SimEvent {
+    NearStation { plane: usize, station: usize },
+    Collision { first: usize, second: usize },
+    Moved { plane: usize, position: Point<f64> },
+}
+
+// État de la simulation. La grille est gardée d'un pas à l'autre et seulement reconstruite,
+// pour ne pas réallouer ses cases à chaque pas.
+struct Simulation {
+    planes: Vec<Plane>,
+    stations: Vec<Station>,
+    collision_radius: f64,
+    grid: SpatialGrid,
+}
 
-        (cross1 * cross2 < 0.0) && (cross3 * cross4 < 0.0)
+impl Simulation {
+    fn new(planes: Vec<Plane>, stations: Vec<Station>, collision_radius: f64) -> Simulation {
+        let grid = SpatialGrid::new(&planes, collision_radius);
+        Simulation { planes, stations, collision_radius, grid }
     }
 
-    fn is_inside_polygon(point: &Point<f64>, polygon: &[Point<f64>]) -> bool {
-        let mut count = 0;
-        let mut j = polygon.len() - 1;
-        for i in 0..polygon.len() {
-            if (polygon[i].y > point.y) != (polygon[j].y > point.y) &&
-               (point.x < (polygon[j].x - polygon[i].x) * (point.y - polygon[i].y) /
-                          (polygon[j].y - polygon[i].y) + polygon[i].x) {
-                count += 1;
+    // Avance tous les avions d'un pas. Les événements sont rangés par type : d'abord les stations
+    // à portée, puis les collisions, enfin les nouvelles positions.
+    fn step(&mut self) -> Vec<SimEvent> {
+        for plane in self.planes.iter_mut() {
+            plane.move_forward();
+        }
+
+        let mut events = Vec::new();
+        for (i, plane) in self.planes.iter().enumerate() {
+            for (j, station) in self.stations.iter().enumerate() {
+                if plane.is_near_station(station) {
+                    events.push(SimEvent::NearStation { plane: i, station: j });
+                }
             }
-            j = i;
         }
-        count % 2 == 1
-    }
-    
-    fn is_collinear(p1: &Point<f64>, p2: &Point<f64>, p3: &Point<f64>) -> bool {
-        let determinant = (p2.x - p1.x) * (p3.y - p1.y) - (p2.y - p1.y) * (p3.x - p1.x);
-        determinant.abs() < 1e-10
+        if self.collision_radius > 0.0 {
+            self.grid.rebuild(&self.planes);
+            for (i, j) in self.grid.potential_collisions(self.collision_radius) {
+                if self.planes[i].is_colliding(&self.planes[j], self.collision_radius) {
+                    events.push(SimEvent::Collision { first: i, second: j });
+                }
+            }
+        }
+        for (i, plane) in self.planes.iter().enumerate() {
+            events.push(SimEvent::Moved { plane: i, position: plane.position.clone() });
+        }
+        events
     }
 }
 
-/*
-(Selon un exo brilliant)
-rac((x - a)² + (y - b)²) = distance entre deux vecteurs quand dans la même direction
- */
-/*fn main() -> Result<(), Box<dyn std::error::Error>> {
-    let _planet = Point { x: 0.0, y: 0.0 };
+fn main() {
+    let planes = vec![
+        Plane { position: Point { x: 0.0, y: 0.0 }, velocity: 1.0, direction: PI / 4.0 },
+        Plane { position: Point { x: 5.0, y: 5.0 }, velocity: 1.2, direction: -PI / 4.0 },
+    ];
+    let stations = vec![Station { position: Point { x: 3.0, y: 3.0 }, radius: 2.0 }];
 
-    let spaceship = Point { x: -5.0, y: 6.0 };
-    let asteroid = Point { x: -3.0, y: 4.5 };
+    let collision_radius = 0.5;
+    let mut simulation = Simulation::new(planes, stations, collision_radius);
+
+    'simulation: for _ in 0..10 {
+        for event in simulation.step() {
+            match event {
+                SimEvent::NearStation { plane, .. } => {
+                    println!("🚀 Plane {} envoie un signal à la station !", plane + 1);
+                }
+                SimEvent::Collision { first, second } => {
+                    println!("💥 Crash entre Plane {} et Plane {} !", first + 1, second + 1);
+                    break 'simulation;
+                }
+                SimEvent::Moved { plane, position } => {
+                    println!("Plane {} position: ({:.2}, {:.2})", plane + 1, position.x, position.y);
+                }
+            }
+        }
+    }
+}
 
-    let d = spaceship.distance(&asteroid);
-    println!("Distance entre le vaisseau et l'astéroïde : {:.2}", d);
 
-    let alert_threshold = 3.0;
-    if d < alert_threshold {
-        println!("🚨 Alerte ! L'astéroïde est trop proche du vaisseau !");
-    } else {
-        println!("✅ Pas de danger immédiat.");
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn pseudo_random_points(count: usize, mut seed: u64) -> Vec<Point<f64>> {
+        let mut next = move || {
+            seed = seed.wrapping_mul(6364136223846793005).wrapping_add(1442695040888963407);
+            (seed >> 11) as f64 / (1u64 << 53) as f64 * 100.0 - 50.0
+        };
+        (0..count).map(|_| Point { x: next(), y: next() }).collect()
     }
 
-    Ok(())
-}*/
+    #[test]
+    fn test_closest_approach_to_polygon() {
+        let square = vec![
+            Point { x: 0.0, y: 0.0 },
+            Point { x: 2.0, y: 0.0 },
+            Point { x: 2.0, y: 2.0 },
+            Point { x: 0.0, y: 2.0 },
+        ];
+        let plane = Plane { position: Point { x: -3.0, y: 2.5 }, velocity: 1.0, direction: 0.0 };
+        let (dist, step) = plane.closest_approach_to_polygon(&square, 10);
+        assert!((dist - 0.5).abs() < 1e-10);
+        assert_eq!(step, 3.0);
 
-impl Plane {
-    fn move_forward(&mut self) {
-        self.position.x += self.velocity * self.direction.cos();
-        self.position.y += self.velocity * self.direction.sin();
+        let inside = Point { x: 1.0, y: 1.5 };
+        assert!((Point::signed_distance_to_polygon(&inside, &square) + 0.5).abs() < 1e-10);
     }
 
-    fn is_near_station(&self, station: &Station) -> bool {
-        let dist = ((self.position.x - station.position.x).powi(2) + 
-                    (self.position.y - station.position.y).powi(2)).sqrt();
-        dist < station.radius
+    #[test]
+    fn test_circles_convex_hull() {
+        let stations = vec![
+            Station { position: Point { x: 0.0, y: 0.0 }, radius: 1.0 },
+            Station { position: Point { x: 5.0, y: 0.0 }, radius: 2.0 },
+        ];
+        let hull = Station::circles_convex_hull(&stations, 8);
+        for station in &stations {
+            for p in Point::circle_to_polygon(&station.position, station.radius, 360) {
+                assert!(Point::signed_distance_to_polygon(&p, &hull) <= 1e-9);
+            }
+        }
     }
 
-    fn is_colliding(&self, other: &Plane, collision_radius: f64) -> bool {
-        let dist = ((self.position.x - other.position.x).powi(2) +
-                    (self.position.y - other.position.y).powi(2)).sqrt();
-        dist < collision_radius
+    #[test]
+    fn test_relative_bearing() {
+        let plane = Plane { position: Point { x: 1.0, y: 1.0 }, velocity: 1.0, direction: 0.0 };
+        let north = Plane { position: Point { x: 1.0, y: 6.0 }, velocity: 1.0, direction: PI };
+        let behind = Plane { position: Point { x: -3.0, y: 1.0 }, velocity: 1.0, direction: 0.0 };
+        assert!((plane.relative_bearing(&north) - 90.0).abs() < 1e-10);
+        assert!((north.relative_bearing(&plane) - 90.0).abs() < 1e-10);
+        assert_eq!(plane.relative_bearing(&behind), 180.0);
     }
-}
 
-fn main() {
-    let mut plane1 = Plane { position: Point { x: 0.0, y: 0.0 }, velocity: 1.0, direction: PI / 4.0 };
-    let mut plane2 = Plane { position: Point { x: 5.0, y: 5.0 }, velocity: 1.2, direction: -PI / 4.0 };
-    let station = Station { position: Point { x: 3.0, y: 3.0 }, radius: 2.0 };
-
-    let collision_radius = 0.5;
+    #[test]
+    fn test_graticule_crossings() {
+        let eastbound = Plane { position: Point { x: 0.5, y: 0.5 }, velocity: 1.0, direction: 0.0 };
+        let crossings = eastbound.graticule_crossings(3);
+        assert_eq!(crossings.len(), 3);
+        for (i, p) in crossings.iter().enumerate() {
+            assert!((p.x - (i + 1) as f64).abs() < 1e-9);
+            assert!((p.y - 0.5).abs() < 0.01);
+        }
 
-    for _ in 0..10 {
-        plane1.move_forward();
-        plane2.move_forward();
+        let northbound = Plane { position: Point { x: 0.5, y: 0.5 }, velocity: 1.0, direction: PI / 2.0 };
+        let crossings = northbound.graticule_crossings(2);
+        assert_eq!(crossings.len(), 2);
+        assert!(crossings.iter().all(|p| (p.x - 0.5).abs() < 1e-9));
+        assert!((crossings[0].y - 1.0).abs() < 1e-9 && (crossings[1].y - 2.0).abs() < 1e-9);
+    }
 
-        if plane1.is_near_station(&station) {
-            println!("🚀 Plane 1 envoie un signal à la station !");
-        }
-        if plane2.is_near_station(&station) {
-            println!("🚀 Plane 2 envoie un signal à la station !");
+    #[test]
+    fn test_collides_within() {
+        let a = Plane { position: Point { x: 0.0, y: 0.0 }, velocity: 1.0, direction: 0.0 };
+        let b = Plane { position: Point { x: 10.0, y: 1.0 }, velocity: 0.5, direction: PI };
+
+        let dt = 1e-4;
+        let mut t = 0.0;
+        while t < 20.0 {
+            let pa = a.position.clone() + a.velocity_vector() * t;
+            let pb = b.position.clone() + b.velocity_vector() * t;
+            if pa.distance(&pb) < 1.5 {
+                break;
+            }
+            t += dt;
         }
+        let exact = a.collides_within(&b, 1.5, 20.0).unwrap();
+        assert!((exact - t).abs() < 2.0 * dt);
+        assert_eq!(a.collides_within(&b, 1.5, exact / 2.0), None);
+
+        let diverging = Plane { position: Point { x: -5.0, y: 0.0 }, velocity: 1.0, direction: PI };
+        assert_eq!(a.collides_within(&diverging, 1.0, 100.0), None);
+    }
 
-        if plane1.is_colliding(&plane2, collision_radius) {
-            println!("💥 Crash entre Plane 1 et Plane 2 !");
-            break;
+    #[test]
+    fn test_point_assign_operators() {
+        let mut p = Point { x: 3, y: -4 };
+        p += Point { x: 1, y: 1 };
+        assert_eq!((p.x, p.y), (4, -3));
+        p -= Point { x: 2, y: 2 };
+        assert_eq!((p.x, p.y), (2, -5));
+        let n = -p;
+        assert_eq!((n.x, n.y), (-2, 5));
+
+        let half = Point { x: 3.0, y: -1.0 } / 2.0;
+        assert_eq!((half.x, half.y), (1.5, -0.5));
+        let inf = Point { x: 1.0f64, y: 0.0 } / 0.0;
+        assert!(inf.x.is_infinite() && inf.y.is_nan());
+
+        let mut plane = Plane { position: Point { x: 1.0, y: 1.0 }, velocity: 2.0, direction: PI / 2.0 };
+        plane.move_forward();
+        assert!((plane.position.x - 1.0).abs() < 1e-12 && (plane.position.y - 3.0).abs() < 1e-12);
+    }
+
+    #[test]
+    fn test_time_to_collision() {
+        let mut a = Plane { position: Point { x: 0.0, y: 0.0 }, velocity: 1.0, direction: PI / 4.0 };
+        let mut b = Plane { position: Point { x: 10.0, y: 0.0 }, velocity: 1.0, direction: 3.0 * PI / 4.0 };
+        let t = a.time_to_collision(&b, 0.5).unwrap();
+        assert!(t > 5.0);
+
+        let mut step = 0;
+        while !a.is_colliding(&b, 0.5) {
+            a.move_forward();
+            b.move_forward();
+            step += 1;
         }
+        assert_eq!(step, t.ceil() as usize);
+        assert_eq!(a.time_to_collision(&b, 0.5), Some(0.0));
 
-        println!("Plane 1 position: ({:.2}, {:.2})", plane1.position.x, plane1.position.y);
-        println!("Plane 2 position: ({:.2}, {:.2})", plane2.position.x, plane2.position.y);
+        let parallel = Plane { position: Point { x: 0.0, y: 5.0 }, velocity: 1.0, direction: PI / 4.0 };
+        assert_eq!(a.time_to_collision(&parallel, 0.5), None);
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn test_serde_round_trip() {
+        let station = Station { position: Point { x: 3.0, y: -1.25 }, radius: 2.5 };
+        let json = serde_json::to_string(&station).unwrap();
+        let parsed: Station = serde_json::from_str(&json).unwrap();
+        assert_eq!(parsed, station);
+
+        let plane = Plane { position: Point { x: 0.1, y: 0.2 }, velocity: 1.2, direction: PI / 3.0 };
+        let parsed: Plane = serde_json::from_str(&serde_json::to_string(&plane).unwrap()).unwrap();
+        assert_eq!(parsed, plane);
     }
-}
 
+    #[test]
+    fn test_intersection_points() {
+        let station = |x: f64, y: f64, radius: f64| Station { position: Point { x, y }, radius };
+        let a = station(0.0, 0.0, 1.0);
+
+        let points = a.intersection_points(&station(1.0, 0.0, 1.0));
+        let h = 3.0f64.sqrt() / 2.0;
+        assert_eq!(points.len(), 2);
+        assert!(points[0].approx_eq(&Point { x: 0.5, y: h }, 1e-12));
+        assert!(points[1].approx_eq(&Point { x: 0.5, y: -h }, 1e-12));
+
+        let tangent = a.intersection_points(&station(2.0, 0.0, 1.0));
+        assert_eq!(tangent.len(), 1);
+        assert!(tangent[0].approx_eq(&Point { x: 1.0, y: 0.0 }, 1e-12));
+        let inner_tangent = a.intersection_points(&station(0.5, 0.0, 0.5));
+        assert_eq!(inner_tangent.len(), 1);
+        assert!(inner_tangent[0].approx_eq(&Point { x: 1.0, y: 0.0 }, 1e-12));
+
+        assert!(a.intersection_points(&station(3.0, 0.0, 1.0)).is_empty());
+        assert!(a.intersection_points(&station(0.2, 0.0, 0.3)).is_empty());
+        assert!(a.intersection_points(&station(0.0, 0.0, 1.0)).is_empty());
+    }
 
-#[cfg(test)]
-mod tests {
-    use super::*;
+    #[test]
+    fn test_spatial_grid() {
+        let mut planes: Vec<Plane> = pseudo_random_points(200, 7)
+            .into_iter()
+            .enumerate()
+            .map(|(i, position)| Plane { position, velocity: 0.5, direction: i as f64 })
+            .collect();
+        let radius = 3.0;
+        let mut grid = SpatialGrid::new(&planes, radius);
+
+        for _ in 0..3 {
+            let candidates = grid.potential_collisions(radius);
+            assert!(candidates.len() < planes.len() * (planes.len() - 1) / 2);
+            for i in 0..planes.len() {
+                for j in i + 1..planes.len() {
+                    if planes[i].is_colliding(&planes[j], radius) {
+                        assert!(candidates.binary_search(&(i, j)).is_ok());
+                    }
+                }
+            }
+            for plane in &mut planes {
+                plane.move_forward();
+            }
+            grid.rebuild(&planes);
+        }
+
+        let far = vec![
+            Plane { position: Point { x: 0.0, y: 0.0 }, velocity: 0.0, direction: 0.0 },
+            Plane { position: Point { x: 100.0, y: 0.0 }, velocity: 0.0, direction: 0.0 },
+        ];
+        assert!(SpatialGrid::new(&far, 1.0).potential_collisions(1.0).is_empty());
+    }
 
     #[test]
-    fn test_transform() {
-        let v1 = Point { x: 3.0, y: 4.0 };
-        let matrix = [[2.0, 0.0], [0.0, 2.0]];
-        let transformed = v1.transform(matrix);
-        assert_eq!(transformed.x, 6.0);
-        assert_eq!(transformed.y, 8.0);
+    fn test_steering() {
+        let mut plane = Plane { position: Point { x: 1.0, y: 1.0 }, velocity: 2.0, direction: 0.0 };
+        plane.turn(-PI / 2.0);
+        assert!((plane.direction - 3.0 * PI / 2.0).abs() < 1e-12);
+        plane.turn(5.0 * PI / 2.0);
+        assert!(plane.direction.abs() < 1e-12);
+        plane.turn(-1e-18);
+        assert!(plane.direction >= 0.0 && plane.direction < 2.0 * PI);
+
+        plane.set_heading_towards(&Point { x: 1.0, y: 4.0 });
+        assert!((plane.direction - PI / 2.0).abs() < 1e-12);
+        plane.set_heading_towards(&Point { x: 0.0, y: 0.0 });
+        assert!((plane.direction - 5.0 * PI / 4.0).abs() < 1e-12);
+
+        let target = Point { x: 4.0, y: -3.0 };
+        plane.set_heading_towards(&target);
+        let before = plane.position.distance(&target);
+        plane.move_forward();
+        assert!((plane.position.distance(&target) - (before - 2.0)).abs() < 1e-12);
     }
 
     #[test]
-    fn test_is_intersecting() {
-        let a1 = Point { x: 1.0, y: 1.0 };
-        let a2 = Point { x: 4.0, y: 4.0 };
-        let b1 = Point { x: 1.0, y: 4.0 };
-        let b2 = Point { x: 4.0, y: 1.0 };
-        assert!(Point::is_intersecting(&a1, &a2, &b1, &b2));
+    fn test_signal_strength() {
+        let station = Station { position: Point { x: 3.0, y: 3.0 }, radius: 2.0 };
+        let at = |x: f64, y: f64| Plane { position: Point { x, y }, velocity: 0.0, direction: 0.0 };
+
+        assert_eq!(at(3.0, 3.0).signal_strength(&station), 1.0);
+        assert_eq!(at(4.0, 3.0).signal_strength(&station), 0.5);
+        assert_eq!(at(3.0, 1.5).signal_strength(&station), 0.25);
+        assert_eq!(at(5.0, 3.0).signal_strength(&station), 0.0);
+        assert_eq!(at(9.0, 9.0).signal_strength(&station), 0.0);
+
+        assert!(at(4.9, 3.0).is_near_station(&station));
+        assert!(!at(5.0, 3.0).is_near_station(&station));
+        let silent = Station { position: Point { x: 0.0, y: 0.0 }, radius: 0.0 };
+        assert!(!at(0.0, 0.0).is_near_station(&silent));
     }
 
     #[test]
-    fn test_is_inside_polygon() {
-        let polygon = vec![
-            Point { x: 0.0, y: 0.0 },
-            Point { x: 5.0, y: 0.0 },
-            Point { x: 5.0, y: 5.0 },
-            Point { x: 0.0, y: 5.0 },
+    fn test_polar() {
+        let p = Point { x: -3.0, y: 4.0 };
+        let (radius, angle) = p.to_polar();
+        assert_eq!(radius, 5.0);
+        assert!(Point::from_polar(radius, angle).approx_eq(&p, 1e-12));
+
+        assert!(Point::from_polar(2.0, PI / 2.0).approx_eq(&Point { x: 0.0, y: 2.0 }, 1e-12));
+        assert_eq!(Point { x: 0.0, y: -1.0 }.to_polar(), (1.0, -PI / 2.0));
+
+        let plane = Plane { position: Point { x: 0.0, y: 0.0 }, velocity: 1.5, direction: PI / 3.0 };
+        let (speed, heading) = plane.velocity_vector().to_polar();
+        assert!((speed - 1.5).abs() < 1e-12 && (heading - PI / 3.0).abs() < 1e-12);
+    }
+
+    #[test]
+    fn test_nearest_station() {
+        let plane = Plane { position: Point { x: 1.0, y: 1.0 }, velocity: 1.0, direction: 0.0 };
+        let stations = vec![
+            Station { position: Point { x: 10.0, y: 1.0 }, radius: 20.0 },
+            Station { position: Point { x: 1.0, y: -2.0 }, radius: 1.0 },
+            Station { position: Point { x: -4.0, y: 1.0 }, radius: 2.0 },
         ];
-        let inside = Point { x: 3.0, y: 3.0 };
-        let outside = Point { x: 6.0, y: 3.0 };
-        assert!(Point::is_inside_polygon(&inside, &polygon));
-        assert!(!Point::is_inside_polygon(&outside, &polygon));
+        let (nearest, distance) = plane.nearest_station(&stations).unwrap();
+        assert_eq!(nearest, &stations[1]);
+        assert_eq!(distance, 3.0);
+        assert!(plane.nearest_station(&[]).is_none());
     }
 
     #[test]
-    fn test_is_collinear() {
-        let v1 = Point { x: 3.0, y: 4.0 };
-        let v2 = Point { x: 5.0, y: 12.0 };
-        assert!(Point::is_collinear(&v1, &v2, &Point { x: 7.0, y: 20.0 }));
-        assert!(!Point::is_collinear(&v1, &v2, &Point { x: 7.0, y: 19.0 }));
+    fn test_step() {
+        let planes = vec![
+            Plane { position: Point { x: 0.0, y: 0.0 }, velocity: 1.0, direction: 0.0 },
+            Plane { position: Point { x: 6.0, y: 0.0 }, velocity: 1.0, direction: PI },
+            Plane { position: Point { x: 0.0, y: 50.0 }, velocity: 0.0, direction: 0.0 },
+        ];
+        let stations = vec![Station { position: Point { x: 0.0, y: 50.0 }, radius: 1.0 }];
+
+        let mut simulation = Simulation::new(planes, stations, 0.5);
+        let events = simulation.step();
+        assert_eq!(
+            events,
+            vec![
+                SimEvent::NearStation { plane: 2, station: 0 },
+                SimEvent::Moved { plane: 0, position: Point { x: 1.0, y: 0.0 } },
+                SimEvent::Moved { plane: 1, position: simulation.planes[1].position.clone() },
+                SimEvent::Moved { plane: 2, position: Point { x: 0.0, y: 50.0 } },
+            ]
+        );
+
+        simulation.step();
+        let events = simulation.step();
+        assert!(events.contains(&SimEvent::Collision { first: 0, second: 1 }));
+        assert_eq!(events.iter().filter(|e| matches!(e, SimEvent::Moved { .. })).count(), 3);
     }
 }
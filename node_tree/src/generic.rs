@@ -0,0 +1,162 @@
+// Homogeneous counterpart of the `Box<dyn Value>` tree: every node holds a `T`, so values
+// keep their type and no downcasting is needed.
+use std::rc::Rc;
+use std::cell::RefCell;
+use std::fmt;
+
+#[derive(Debug)]
+pub struct Node<T> {
+    pub value: T,
+    pub left: Option<Rc<RefCell<Node<T>>>>,
+    pub right: Option<Rc<RefCell<Node<T>>>>,
+}
+
+impl<T> Node<T> {
+    pub fn new(value: T) -> Self {
+        Node {
+            value,
+            left: None,
+            right: None,
+        }
+    }
+
+    pub fn add_left(&mut self, value: Rc<RefCell<Node<T>>>) {
+        self.left = Some(value);
+    }
+
+    pub fn add_right(&mut self, value: Rc<RefCell<Node<T>>>) {
+        self.right = Some(value);
+    }
+
+    pub fn set_children(&mut self, left: Option<Rc<RefCell<Node<T>>>>, right: Option<Rc<RefCell<Node<T>>>>) {
+        self.left = left;
+        self.right = right;
+    }
+}
+
+pub fn new_node<T>(value: T) -> Rc<RefCell<Node<T>>> {
+    Rc::new(RefCell::new(Node::new(value)))
+}
+
+pub fn print_tree<T: fmt::Display>(node: Rc<RefCell<Node<T>>>, depth: usize) {
+    let borrowed_node = node.borrow();
+    let indent = "-".repeat(depth * 2);
+    println!("{}Node: {}", indent, borrowed_node.value);
+
+    if let Some(ref left) = borrowed_node.left {
+        print_tree(left.clone(), depth + 1);
+    }
+
+    if let Some(ref right) = borrowed_node.right {
+        print_tree(right.clone(), depth + 1);
+    }
+}
+
+pub fn build_tree_from_list<T: Clone>(values: &[T]) -> Option<Rc<RefCell<Node<T>>>> {
+    fn helper<T: Clone>(values: &[T], start: usize, end: usize) -> Option<Rc<RefCell<Node<T>>>> {
+        if start > end {
+            return None;
+        }
+
+        let mid = (start + end) / 2;
+        let node = new_node(values[mid].clone());
+
+        if mid > start {
+            node.borrow_mut().left = helper(values, start, mid - 1);
+        }
+
+        if mid < end {
+            node.borrow_mut().right = helper(values, mid + 1, end);
+        }
+
+        Some(node)
+    }
+
+    if values.is_empty() {
+        None
+    } else {
+        helper(values, 0, values.len() - 1)
+    }
+}
+
+pub fn inorder_traversal<T: fmt::Display>(node: Option<Rc<RefCell<Node<T>>>>) {
+    if let Some(n) = node {
+        let n = n.borrow();
+        inorder_traversal(n.left.clone());
+        println!("{}", n.value);
+        inorder_traversal(n.right.clone());
+    }
+}
+
+// Binary search tree insertion: smaller values go left, others (duplicates included) go right
+pub fn insert<T: Ord>(root: &Rc<RefCell<Node<T>>>, value: T) {
+    let mut n = root.borrow_mut();
+    let child = if value < n.value { &mut n.left } else { &mut n.right };
+    match child {
+        Some(child) => insert(child, value),
+        None => *child = Some(new_node(value)),
+    }
+}
+
+pub fn height<T>(node: Option<Rc<RefCell<Node<T>>>>) -> usize {
+    if let Some(n) = node {
+        let n = n.borrow();
+        1 + usize::max(height(n.left.clone()), height(n.right.clone()))
+    } else {
+        0
+    }
+}
+
+pub fn find_value<T: PartialEq>(node: Option<Rc<RefCell<Node<T>>>>, target: &T) -> bool {
+    if let Some(n) = node {
+        let n = n.borrow();
+        n.value == *target || find_value(n.left.clone(), target) || find_value(n.right.clone(), target)
+    } else {
+        false
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_generic_tree() {
+        let root = new_node(String::from("root"));
+        root.borrow_mut().set_children(Some(new_node(String::from("left"))), None);
+        root.borrow_mut().add_right(new_node(String::from("right")));
+        let left: String = root.borrow().left.as_ref().unwrap().borrow().value.clone();
+        assert_eq!(left, "left");
+        assert!(find_value(Some(root.clone()), &String::from("right")));
+        assert!(!find_value(Some(root.clone()), &String::from("missing")));
+        assert_eq!(height(Some(root)), 2);
+
+        let numbers = build_tree_from_list(&[1, 2, 3, 4, 5, 6, 7]).unwrap();
+        let value: i32 = numbers.borrow().value;
+        assert_eq!(value, 4);
+        assert_eq!(height(Some(numbers.clone())), 3);
+        assert!(find_value(Some(numbers), &7));
+        assert!(build_tree_from_list::<i32>(&[]).is_none());
+    }
+
+    #[test]
+    fn test_insert() {
+        fn inorder(node: Option<Rc<RefCell<Node<i32>>>>, output: &mut Vec<i32>) {
+            if let Some(n) = node {
+                let n = n.borrow();
+                inorder(n.left.clone(), output);
+                output.push(n.value);
+                inorder(n.right.clone(), output);
+            }
+        }
+
+        let root = new_node(5);
+        for value in [3, 8, 1, 4, 8] {
+            insert(&root, value);
+        }
+        let mut values = Vec::new();
+        inorder(Some(root.clone()), &mut values);
+        assert_eq!(values, vec![1, 3, 4, 5, 8, 8]);
+        assert_eq!(height(Some(root)), 3);
+    }
+}
@@ -1,18 +1,29 @@
 #![allow(dead_code)]
+mod generic;
+
 use std::rc::Rc;
 use std::cell::RefCell;
 use std::fmt;
 use std::any::Any;
+use std::collections::VecDeque;
 
 trait Value: Any {
-    fn display(&self);
+    fn label(&self) -> String;
+    fn clone_box(&self) -> Box<dyn Value>;
+    fn display(&self) {
+        println!("{}", self.label());
+    }
     fn equals(&self, other: &dyn Value) -> bool;
     fn as_any(&self) -> &dyn Any;
 }
 
 impl Value for i32 {
-    fn display(&self) {
-        println!("i32 value: {}", self);
+    fn label(&self) -> String {
+        format!("i32 value: {}", self)
+    }
+
+    fn clone_box(&self) -> Box<dyn Value> {
+        Box::new(*self)
     }
     
     fn equals(&self, other: &dyn Value) -> bool {
@@ -28,8 +39,12 @@ impl Value for i32 {
 }
 
 impl Value for String {
-    fn display(&self) {
-        println!("String value: {}", self);
+    fn label(&self) -> String {
+        format!("String value: {}", self)
+    }
+
+    fn clone_box(&self) -> Box<dyn Value> {
+        Box::new(self.clone())
     }
 
     fn equals(&self, other: &dyn Value) -> bool {
@@ -63,8 +78,12 @@ impl Value for String {
 }*/
 
 impl Value for f32 {
-    fn display(&self) {
-        println!("Float value: {}", self);
+    fn label(&self) -> String {
+        format!("Float value: {}", self)
+    }
+
+    fn clone_box(&self) -> Box<dyn Value> {
+        Box::new(*self)
     }
 
     fn equals(&self, other: &dyn Value) -> bool {
@@ -81,8 +100,7 @@ impl Value for f32 {
 
 impl fmt::Debug for dyn Value {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        self.display();
-        write!(f, "")
+        write!(f, "{}", self.label())
     }
 }
 
@@ -91,6 +109,7 @@ struct Node {
     value: Box<dyn Value>,
     left: Option<Rc<RefCell<Node>>>,
     right: Option<Rc<RefCell<Node>>>,
+    subtree_sum: Option<f64>, // Filled in by annotate_subtree_sums
 }
 
 impl Node {
@@ -99,6 +118,7 @@ impl Node {
             value,
             left: None,
             right: None,
+            subtree_sum: None,
         }
     }
 
@@ -139,6 +159,36 @@ fn print_tree(node: Rc<RefCell<Node>>, depth: usize) {
     }
 }
 
+// Same output as print_tree, unless the tree has a cycle: then nothing is printed and
+// false is returned instead of recursing forever
+fn print_tree_checked(node: Rc<RefCell<Node>>) -> bool {
+    if has_cycle(Some(node.clone())) {
+        return false;
+    }
+    print_tree(node, 0);
+    true
+}
+
+// True when a node is reachable from itself, i.e. the same Rc shows up twice on one
+// root-to-leaf path. Shared subtrees that don't loop back are fine.
+fn has_cycle(root: Option<Rc<RefCell<Node>>>) -> bool {
+    fn walk(node: &Rc<RefCell<Node>>, path: &mut Vec<Rc<RefCell<Node>>>) -> bool {
+        if path.iter().any(|ancestor| Rc::ptr_eq(ancestor, node)) {
+            return true;
+        }
+        path.push(node.clone());
+        let (left, right) = {
+            let n = node.borrow();
+            (n.left.clone(), n.right.clone())
+        };
+        let found = [left, right].iter().flatten().any(|child| walk(child, path));
+        path.pop();
+        found
+    }
+
+    root.is_some_and(|root| walk(&root, &mut Vec::new()))
+}
+
 fn build_tree_from_list<T: 'static + Value + Clone>(values: &[T]) -> Option<Rc<RefCell<Node>>> {
     fn helper<T: 'static + Value + Clone>(values: &[T], start: usize, end: usize) -> Option<Rc<RefCell<Node>>> {
         if start > end {
@@ -168,6 +218,14 @@ fn build_tree_from_list<T: 'static + Value + Clone>(values: &[T]) -> Option<Rc<R
     }
 }
 
+// Values of type T in inorder, skipping any of another type. The result is sorted when the
+// tree is in search order, so feeding it back to build_tree_from_list rebalances the tree.
+fn to_sorted_vec<T: Value + Clone>(root: Option<Rc<RefCell<Node>>>) -> Vec<T> {
+    InorderIter::new(root)
+        .filter_map(|n| n.borrow().value.as_any().downcast_ref::<T>().cloned())
+        .collect()
+}
+
 fn inorder_traversal(node: Option<Rc<RefCell<Node>>>) {
     if let Some(n) = node {
         let n = n.borrow();
@@ -177,6 +235,65 @@ fn inorder_traversal(node: Option<Rc<RefCell<Node>>>) {
     }
 }
 
+// Inorder walk with an explicit stack instead of recursion, so deep trees can't overflow
+// the call stack
+struct InorderIter {
+    stack: Vec<Rc<RefCell<Node>>>,
+}
+
+impl InorderIter {
+    fn new(root: Option<Rc<RefCell<Node>>>) -> Self {
+        let mut iter = InorderIter { stack: Vec::new() };
+        iter.push_left_spine(root);
+        iter
+    }
+
+    fn push_left_spine(&mut self, mut node: Option<Rc<RefCell<Node>>>) {
+        while let Some(n) = node {
+            node = n.borrow().left.clone();
+            self.stack.push(n);
+        }
+    }
+}
+
+impl Iterator for InorderIter {
+    type Item = Rc<RefCell<Node>>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let node = self.stack.pop()?;
+        let right = node.borrow().right.clone();
+        self.push_left_spine(right);
+        Some(node)
+    }
+}
+
+fn preorder_traversal(node: Option<Rc<RefCell<Node>>>) {
+    preorder_with(node, &mut |value| value.display());
+}
+
+fn postorder_traversal(node: Option<Rc<RefCell<Node>>>) {
+    postorder_with(node, &mut |value| value.display());
+}
+
+// Same walks as the traversals above, handing each value to `visit` instead of displaying it
+fn preorder_with(node: Option<Rc<RefCell<Node>>>, visit: &mut dyn FnMut(&dyn Value)) {
+    if let Some(n) = node {
+        let n = n.borrow();
+        visit(n.value.as_ref());
+        preorder_with(n.left.clone(), visit);
+        preorder_with(n.right.clone(), visit);
+    }
+}
+
+fn postorder_with(node: Option<Rc<RefCell<Node>>>, visit: &mut dyn FnMut(&dyn Value)) {
+    if let Some(n) = node {
+        let n = n.borrow();
+        postorder_with(n.left.clone(), visit);
+        postorder_with(n.right.clone(), visit);
+        visit(n.value.as_ref());
+    }
+}
+
 fn height(node: Option<Rc<RefCell<Node>>>) -> usize {
     if let Some(n) = node {
         let n = n.borrow();
@@ -186,6 +303,52 @@ fn height(node: Option<Rc<RefCell<Node>>>) -> usize {
     }
 }
 
+fn size(node: Option<Rc<RefCell<Node>>>) -> usize {
+    if let Some(n) = node {
+        let n = n.borrow();
+        1 + size(n.left.clone()) + size(n.right.clone())
+    } else {
+        0
+    }
+}
+
+// Number of nodes on the longest path between any two nodes, found in one bottom-up pass
+fn diameter(root: Option<Rc<RefCell<Node>>>) -> usize {
+    // Returns the height of `node` and records the longest path going through it
+    fn walk(node: Option<Rc<RefCell<Node>>>, longest: &mut usize) -> usize {
+        if let Some(n) = node {
+            let n = n.borrow();
+            let left = walk(n.left.clone(), longest);
+            let right = walk(n.right.clone(), longest);
+            *longest = usize::max(*longest, left + right + 1);
+            1 + usize::max(left, right)
+        } else {
+            0
+        }
+    }
+
+    let mut longest = 0;
+    walk(root, &mut longest);
+    longest
+}
+
+// True when subtree heights differ by at most one at every node
+fn is_balanced(root: Option<Rc<RefCell<Node>>>) -> bool {
+    // Height of a balanced subtree, None as soon as an unbalanced one turns up
+    fn checked_height(node: Option<Rc<RefCell<Node>>>) -> Option<usize> {
+        if let Some(n) = node {
+            let n = n.borrow();
+            let left = checked_height(n.left.clone())?;
+            let right = checked_height(n.right.clone())?;
+            (left.abs_diff(right) <= 1).then_some(1 + usize::max(left, right))
+        } else {
+            Some(0)
+        }
+    }
+
+    checked_height(root).is_some()
+}
+
 fn find_value(node: Option<Rc<RefCell<Node>>>, target: &dyn Value) -> bool {
     if let Some(n) = node {
         let n = n.borrow();
@@ -200,6 +363,535 @@ fn find_value(node: Option<Rc<RefCell<Node>>>, target: &dyn Value) -> bool {
     }
 }
 
+// Nodes from the root down to the node holding `target`. With duplicates, the first match
+// in preorder wins, so a match higher up or further left is preferred.
+fn find_path(root: Option<Rc<RefCell<Node>>>, target: &dyn Value) -> Option<Vec<Rc<RefCell<Node>>>> {
+    fn walk(node: Option<Rc<RefCell<Node>>>, target: &dyn Value, path: &mut Vec<Rc<RefCell<Node>>>) -> bool {
+        let Some(node) = node else {
+            return false;
+        };
+        path.push(node.clone());
+        let n = node.borrow();
+        if n.value.equals(target) || walk(n.left.clone(), target, path) || walk(n.right.clone(), target, path) {
+            return true;
+        }
+        path.pop();
+        false
+    }
+
+    let mut path = Vec::new();
+    walk(root, target, &mut path).then_some(path)
+}
+
+// Removes the first node holding `target` (preorder, like find_path) and returns the new
+// root. A single child takes the node's place; with two children the inorder successor
+// is unlinked and promoted. The removed node gives up its children so it keeps no part
+// of the tree alive.
+fn delete(root: Option<Rc<RefCell<Node>>>, target: &dyn Value) -> Option<Rc<RefCell<Node>>> {
+    // Subtree that takes the place of `node`
+    fn replacement(node: &Rc<RefCell<Node>>) -> Option<Rc<RefCell<Node>>> {
+        let mut n = node.borrow_mut();
+        match (n.left.take(), n.right.take()) {
+            (None, only) | (only, None) => only,
+            (Some(left), Some(right)) => {
+                let (successor, rest) = if right.borrow().left.is_none() {
+                    let rest = right.borrow_mut().right.take();
+                    (right, rest)
+                } else {
+                    let mut parent = right.clone();
+                    loop {
+                        let next = parent.borrow().left.clone().unwrap();
+                        if next.borrow().left.is_none() {
+                            parent.borrow_mut().left = next.borrow_mut().right.take();
+                            break (next, Some(right));
+                        }
+                        parent = next;
+                    }
+                };
+                successor.borrow_mut().set_children(Some(left), rest);
+                Some(successor)
+            }
+        }
+    }
+
+    fn remove_from(slot: &mut Option<Rc<RefCell<Node>>>, target: &dyn Value) -> bool {
+        let Some(node) = slot.clone() else {
+            return false;
+        };
+        if node.borrow().value.equals(target) {
+            *slot = replacement(&node);
+            return true;
+        }
+        let mut n = node.borrow_mut();
+        let n = &mut *n;
+        remove_from(&mut n.left, target) || remove_from(&mut n.right, target)
+    }
+
+    let mut root = root;
+    remove_from(&mut root, target);
+    root
+}
+
+// Deepest node whose subtree holds both values, None if either one is missing
+fn lowest_common_ancestor(root: Option<Rc<RefCell<Node>>>, a: &dyn Value, b: &dyn Value) -> Option<Rc<RefCell<Node>>> {
+    // Returns the LCA once both values are below `node`, otherwise whichever one was found
+    fn search(node: Option<Rc<RefCell<Node>>>, a: &dyn Value, b: &dyn Value) -> Option<Rc<RefCell<Node>>> {
+        let node = node?;
+        let n = node.borrow();
+        if n.value.equals(a) || n.value.equals(b) {
+            return Some(node.clone());
+        }
+
+        match (search(n.left.clone(), a, b), search(n.right.clone(), a, b)) {
+            (Some(_), Some(_)) => Some(node.clone()),
+            (left, right) => left.or(right),
+        }
+    }
+
+    if !find_value(root.clone(), a) || !find_value(root.clone(), b) {
+        return None;
+    }
+    search(root, a, b)
+}
+
+// Groups nodes by diagonal: a right child stays on its parent's diagonal, a left child
+// starts the next one. Groups are ordered by diagonal, nodes in preorder within a group.
+fn diagonal_groups(root: Option<Rc<RefCell<Node>>>) -> Vec<Vec<Rc<RefCell<Node>>>> {
+    fn collect(node: Option<Rc<RefCell<Node>>>, diagonal: usize, groups: &mut Vec<Vec<Rc<RefCell<Node>>>>) {
+        if let Some(n) = node {
+            if groups.len() <= diagonal {
+                groups.push(Vec::new());
+            }
+            groups[diagonal].push(n.clone());
+            let n = n.borrow();
+            collect(n.left.clone(), diagonal + 1, groups);
+            collect(n.right.clone(), diagonal, groups);
+        }
+    }
+
+    let mut groups = Vec::new();
+    collect(root, 0, &mut groups);
+    groups
+}
+
+// Pairs every node with left height minus right height, in post-order. Heights are
+// computed in the same pass so the whole tree is visited once.
+fn balance_factors(root: Option<Rc<RefCell<Node>>>) -> Vec<(Rc<RefCell<Node>>, i32)> {
+    fn collect(node: Option<Rc<RefCell<Node>>>, factors: &mut Vec<(Rc<RefCell<Node>>, i32)>) -> i32 {
+        if let Some(n) = node {
+            let (left, right) = {
+                let b = n.borrow();
+                (b.left.clone(), b.right.clone())
+            };
+            let left_height = collect(left, factors);
+            let right_height = collect(right, factors);
+            factors.push((n, left_height - right_height));
+            1 + i32::max(left_height, right_height)
+        } else {
+            0
+        }
+    }
+
+    let mut factors = Vec::new();
+    collect(root, &mut factors);
+    factors
+}
+
+// Independent copy of the tree: every node and value is duplicated, nothing is shared
+fn deep_clone(root: Option<Rc<RefCell<Node>>>) -> Option<Rc<RefCell<Node>>> {
+    root.map(|n| {
+        let n = n.borrow();
+        let mut copy = Node::new(n.value.clone_box());
+        copy.set_children(deep_clone(n.left.clone()), deep_clone(n.right.clone()));
+        copy.subtree_sum = n.subtree_sum;
+        Rc::new(RefCell::new(copy))
+    })
+}
+
+// Same shape, and `Value::equals` holds at every pair of matching nodes
+fn trees_equal(a: Option<Rc<RefCell<Node>>>, b: Option<Rc<RefCell<Node>>>) -> bool {
+    match (a, b) {
+        (None, None) => true,
+        (Some(a), Some(b)) => {
+            let (a, b) = (a.borrow(), b.borrow());
+            a.value.equals(b.value.as_ref())
+                && trees_equal(a.left.clone(), b.left.clone())
+                && trees_equal(a.right.clone(), b.right.clone())
+        }
+        _ => false,
+    }
+}
+
+// Swaps left and right children at every node, in place
+fn mirror(node: Option<Rc<RefCell<Node>>>) {
+    if let Some(n) = node {
+        let mut n = n.borrow_mut();
+        let n = &mut *n;
+        std::mem::swap(&mut n.left, &mut n.right);
+        mirror(n.left.clone());
+        mirror(n.right.clone());
+    }
+}
+
+// Breadth-first walk: one inner vector per depth, nodes left to right
+fn level_order_nodes(root: Option<Rc<RefCell<Node>>>) -> Vec<Vec<Rc<RefCell<Node>>>> {
+    let mut levels = Vec::new();
+    let mut queue: VecDeque<Rc<RefCell<Node>>> = root.into_iter().collect();
+    while !queue.is_empty() {
+        let mut level = Vec::with_capacity(queue.len());
+        for _ in 0..queue.len() {
+            let node = queue.pop_front().unwrap();
+            {
+                let n = node.borrow();
+                queue.extend(n.left.clone());
+                queue.extend(n.right.clone());
+            }
+            level.push(node);
+        }
+        levels.push(level);
+    }
+    levels
+}
+
+fn level_order(root: Option<Rc<RefCell<Node>>>) -> Vec<Vec<Box<dyn Value>>> {
+    level_order_nodes(root)
+        .iter()
+        .map(|level| level.iter().map(|n| n.borrow().value.clone_box()).collect())
+        .collect()
+}
+
+fn print_level_order(root: Option<Rc<RefCell<Node>>>) {
+    for (depth, level) in level_order_nodes(root).iter().enumerate() {
+        println!("Level {}:", depth);
+        for node in level {
+            node.borrow().value.display();
+        }
+    }
+}
+
+fn numeric_value(value: &dyn Value) -> Option<f64> {
+    let any = value.as_any();
+    if let Some(v) = any.downcast_ref::<i32>() {
+        Some(*v as f64)
+    } else {
+        any.downcast_ref::<f32>().map(|v| *v as f64)
+    }
+}
+
+// Stores in each node the sum of the numeric values of its subtree, non-numeric values count as 0
+fn annotate_subtree_sums(root: Option<Rc<RefCell<Node>>>) -> f64 {
+    if let Some(n) = root {
+        let mut n = n.borrow_mut();
+        let sum = numeric_value(n.value.as_ref()).unwrap_or(0.0)
+            + annotate_subtree_sums(n.left.clone())
+            + annotate_subtree_sums(n.right.clone());
+        n.subtree_sum = Some(sum);
+        sum
+    } else {
+        0.0
+    }
+}
+
+// Cached sum, None until annotate_subtree_sums has run on this node
+fn subtree_sum(node: Option<Rc<RefCell<Node>>>) -> Option<f64> {
+    node.and_then(|n| n.borrow().subtree_sum)
+}
+
+// Graphviz DOT description of the tree. Nodes are numbered in preorder and labelled with
+// `Value::label`; edges carry a left/right label.
+fn to_dot(root: Option<Rc<RefCell<Node>>>) -> String {
+    fn write_node(node: &Rc<RefCell<Node>>, next_id: &mut usize, output: &mut String) -> usize {
+        let id = *next_id;
+        *next_id += 1;
+        let n = node.borrow();
+        let label = n.value.label().replace('\\', "\\\\").replace('"', "\\\"");
+        output.push_str(&format!("    n{} [label=\"{}\"];\n", id, label));
+
+        for (child, side) in [(&n.left, "left"), (&n.right, "right")] {
+            if let Some(child) = child {
+                let child_id = write_node(child, next_id, output);
+                output.push_str(&format!("    n{} -> n{} [label=\"{}\"];\n", id, child_id, side));
+            }
+        }
+        id
+    }
+
+    let mut output = String::from("digraph tree {\n");
+    if let Some(root) = root {
+        write_node(&root, &mut 0, &mut output);
+    }
+    output.push_str("}\n");
+    output
+}
+
+// Outline format: one node per line, two spaces of indentation per level, children
+// listed left then right. Strings are quoted, with `\`, `"` and newlines escaped as
+// `\\`, `\"` and `\n`, and floats always carry a decimal point so the value type survives
+// a round trip. A missing left child followed by a right child is written as `~`.
+fn outline_value(value: &dyn Value) -> String {
+    let any = value.as_any();
+    if let Some(v) = any.downcast_ref::<i32>() {
+        v.to_string()
+    } else if let Some(v) = any.downcast_ref::<String>() {
+        let escaped = v.replace('\\', "\\\\").replace('"', "\\\"").replace('\n', "\\n");
+        format!("\"{}\"", escaped)
+    } else if let Some(v) = any.downcast_ref::<f32>() {
+        format!("{:?}", v)
+    } else {
+        String::from("?")
+    }
+}
+
+fn to_outline(root: Option<Rc<RefCell<Node>>>) -> String {
+    fn write_node(node: &Rc<RefCell<Node>>, depth: usize, output: &mut String) {
+        let n = node.borrow();
+        output.push_str(&format!("{}{}\n", "  ".repeat(depth), outline_value(n.value.as_ref())));
+        match (&n.left, &n.right) {
+            (None, Some(right)) => {
+                output.push_str(&format!("{}~\n", "  ".repeat(depth + 1)));
+                write_node(right, depth + 1, output);
+            }
+            (left, right) => {
+                for child in [left, right].into_iter().flatten() {
+                    write_node(child, depth + 1, output);
+                }
+            }
+        }
+    }
+
+    let mut output = String::new();
+    if let Some(root) = root {
+        write_node(&root, 0, &mut output);
+    }
+    output
+}
+
+fn from_outline(s: &str) -> Option<Rc<RefCell<Node>>> {
+    // Undoes outline_value's escaping, None on a bare quote or an unknown escape
+    fn unescape(text: &str) -> Option<String> {
+        let mut result = String::with_capacity(text.len());
+        let mut chars = text.chars();
+        while let Some(c) = chars.next() {
+            match c {
+                '\\' => match chars.next()? {
+                    '\\' => result.push('\\'),
+                    '"' => result.push('"'),
+                    'n' => result.push('\n'),
+                    _ => return None,
+                },
+                '"' => return None,
+                c => result.push(c),
+            }
+        }
+        Some(result)
+    }
+
+    fn parse_value(text: &str) -> Option<Rc<RefCell<Node>>> {
+        if text.len() >= 2 && text.starts_with('"') && text.ends_with('"') {
+            unescape(&text[1..text.len() - 1]).map(new_node)
+        } else if text.contains(['.', 'e', 'E']) || text.ends_with("inf") || text == "NaN" {
+            text.parse::<f32>().ok().map(new_node)
+        } else {
+            text.parse::<i32>().ok().map(new_node)
+        }
+    }
+
+    // Some(None) stands for a `~` placeholder
+    fn parse_node(lines: &[(usize, &str)], i: &mut usize, depth: usize) -> Option<Option<Rc<RefCell<Node>>>> {
+        let &(line_depth, text) = lines.get(*i)?;
+        if line_depth != depth {
+            return None;
+        }
+        *i += 1;
+        if text == "~" {
+            return Some(None);
+        }
+        let node = parse_value(text)?;
+        let mut children = Vec::new();
+        while children.len() < 2 && lines.get(*i).is_some_and(|&(d, _)| d == depth + 1) {
+            children.push(parse_node(lines, i, depth + 1)?);
+        }
+        let mut children = children.into_iter();
+        node.borrow_mut().set_children(children.next().flatten(), children.next().flatten());
+        Some(Some(node))
+    }
+
+    let mut lines = Vec::new();
+    for line in s.lines().filter(|l| !l.trim().is_empty()) {
+        let text = line.trim_start_matches(' ');
+        let indent = line.len() - text.len();
+        if indent % 2 != 0 {
+            return None;
+        }
+        lines.push((indent / 2, text.trim_end()));
+    }
+    if lines.is_empty() {
+        return None;
+    }
+
+    let mut i = 0;
+    let root = parse_node(&lines, &mut i, 0)?;
+    if i == lines.len() { root } else { None }
+}
+
+// Byte format, preorder: a type tag, the value, then the left and right subtrees. An
+// absent child is a single NO_NODE byte. i32 and f32 are 4 little-endian bytes, a String
+// is its byte length as a little-endian u32 followed by the UTF-8 bytes. Values of any
+// other type can't be encoded and are written as NO_NODE, dropping their subtree.
+const TAG_I32: u8 = 0;
+const TAG_STRING: u8 = 1;
+const TAG_F32: u8 = 2;
+const NO_NODE: u8 = 0xFF;
+
+fn serialize_tree(root: Option<Rc<RefCell<Node>>>) -> Vec<u8> {
+    fn write_node(node: Option<Rc<RefCell<Node>>>, output: &mut Vec<u8>) {
+        let Some(n) = node else {
+            output.push(NO_NODE);
+            return;
+        };
+        let n = n.borrow();
+        let any = n.value.as_any();
+        if let Some(v) = any.downcast_ref::<i32>() {
+            output.push(TAG_I32);
+            output.extend_from_slice(&v.to_le_bytes());
+        } else if let Some(v) = any.downcast_ref::<String>() {
+            output.push(TAG_STRING);
+            output.extend_from_slice(&(v.len() as u32).to_le_bytes());
+            output.extend_from_slice(v.as_bytes());
+        } else if let Some(v) = any.downcast_ref::<f32>() {
+            output.push(TAG_F32);
+            output.extend_from_slice(&v.to_le_bytes());
+        } else {
+            output.push(NO_NODE);
+            return;
+        }
+        write_node(n.left.clone(), output);
+        write_node(n.right.clone(), output);
+    }
+
+    let mut output = Vec::new();
+    write_node(root, &mut output);
+    output
+}
+
+// Deepest tree deserialize_tree accepts, so crafted input can't overflow the stack
+const MAX_DESERIALIZE_DEPTH: usize = 1024;
+
+// Reads one tree off the front of `data` and advances past it. None for an empty tree,
+// malformed input or a tree nested deeper than MAX_DESERIALIZE_DEPTH.
+fn deserialize_tree(data: &mut &[u8]) -> Option<Rc<RefCell<Node>>> {
+    fn take<'a>(data: &mut &'a [u8], len: usize) -> Option<&'a [u8]> {
+        if data.len() < len {
+            return None;
+        }
+        let (head, rest) = data.split_at(len);
+        *data = rest;
+        Some(head)
+    }
+
+    fn take_4(data: &mut &[u8]) -> Option<[u8; 4]> {
+        take(data, 4)?.try_into().ok()
+    }
+
+    // Some(None) stands for a NO_NODE marker
+    fn read_node(data: &mut &[u8], depth: usize) -> Option<Option<Rc<RefCell<Node>>>> {
+        let tag = take(data, 1)?[0];
+        if tag == NO_NODE {
+            return Some(None);
+        }
+        if depth >= MAX_DESERIALIZE_DEPTH {
+            return None;
+        }
+        let node = match tag {
+            TAG_I32 => new_node(i32::from_le_bytes(take_4(data)?)),
+            TAG_STRING => {
+                let len = u32::from_le_bytes(take_4(data)?) as usize;
+                new_node(String::from_utf8(take(data, len)?.to_vec()).ok()?)
+            }
+            TAG_F32 => new_node(f32::from_le_bytes(take_4(data)?)),
+            _ => return None,
+        };
+        let left = read_node(data, depth + 1)?;
+        let right = read_node(data, depth + 1)?;
+        node.borrow_mut().set_children(left, right);
+        Some(Some(node))
+    }
+
+    read_node(data, 0).flatten()
+}
+
+// Tree wrapper remembering the last values found, most recent first
+struct CachedTree {
+    root: Option<Rc<RefCell<Node>>>,
+    capacity: usize,
+    recent: VecDeque<String>,
+    steps: usize, // Nodes visited by lookups, used by the tests
+}
+
+impl CachedTree {
+    fn new(root: Option<Rc<RefCell<Node>>>, capacity: usize) -> Self {
+        CachedTree {
+            root,
+            capacity,
+            recent: VecDeque::with_capacity(capacity),
+            steps: 0,
+        }
+    }
+
+    fn find(&mut self, target: &dyn Value) -> bool {
+        let key = target.label();
+        if let Some(pos) = self.recent.iter().position(|k| *k == key) {
+            let hit = self.recent.remove(pos).unwrap();
+            self.recent.push_front(hit);
+            return true;
+        }
+
+        let found = Self::find_counted(self.root.clone(), target, &mut self.steps);
+        if found && self.capacity > 0 {
+            self.recent.truncate(self.capacity - 1);
+            self.recent.push_front(key);
+        }
+        found
+    }
+
+    fn find_counted(node: Option<Rc<RefCell<Node>>>, target: &dyn Value, steps: &mut usize) -> bool {
+        if let Some(n) = node {
+            *steps += 1;
+            let n = n.borrow();
+            n.value.equals(target)
+                || Self::find_counted(n.left.clone(), target, steps)
+                || Self::find_counted(n.right.clone(), target, steps)
+        } else {
+            false
+        }
+    }
+
+    // Attaches `child` to the first free slot of `parent` (left, then right)
+    fn insert(&mut self, parent: &Rc<RefCell<Node>>, child: Rc<RefCell<Node>>) -> bool {
+        let mut parent = parent.borrow_mut();
+        if parent.left.is_none() {
+            parent.add_left(child);
+        } else if parent.right.is_none() {
+            parent.add_right(child);
+        } else {
+            return false;
+        }
+        self.recent.clear();
+        true
+    }
+
+    // Removes the first node matching `target` (preorder) through `delete`, keeping its
+    // descendants in the tree
+    fn delete(&mut self, target: &dyn Value) -> bool {
+        let removed = find_value(self.root.clone(), target);
+        if removed {
+            self.root = delete(self.root.take(), target);
+            self.recent.clear();
+        }
+        removed
+    }
+}
+
 fn main() {
     let root = new_node(1);
     let left_child = new_node_from_str("Hello");
@@ -217,7 +909,9 @@ fn main() {
     
     right_child.borrow_mut().set_children(Some(right_left_child.clone()), Some(right_right_child.clone()));
 
-    print_tree(root.clone(), 0);
+    if !print_tree_checked(root.clone()) {
+        println!("The tree contains a cycle, not printing it.");
+    }
     
     let target = String::from("Hello");
 
@@ -235,3 +929,443 @@ fn main() {
         println!("Tree Height: {}", tree_height);
     }*/
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sample_tree() -> Rc<RefCell<Node>> {
+        let root = new_node(1);
+        let left_child = new_node_from_str("Hello");
+        let right_child = new_node(3.65);
+        root.borrow_mut().set_children(Some(left_child.clone()), Some(right_child.clone()));
+        left_child.borrow_mut().set_children(Some(new_node(String::from("Howdy"))), Some(new_node(155)));
+        right_child.borrow_mut().set_children(Some(new_node(200)), Some(new_node(String::from("Hi pal !"))));
+        root
+    }
+
+    #[test]
+    fn test_cached_tree() {
+        let root = sample_tree();
+        let mut tree = CachedTree::new(Some(root.clone()), 2);
+        let target = 200;
+
+        assert!(tree.find(&target));
+        let steps = tree.steps;
+        assert!(steps > 0);
+        assert!(tree.find(&target));
+        assert_eq!(tree.steps, steps);
+
+        let leaf = root.borrow().left.clone().unwrap().borrow().left.clone().unwrap();
+        assert!(tree.insert(&leaf, new_node(7)));
+        assert!(tree.find(&target));
+        assert!(tree.steps > steps);
+
+        assert!(tree.delete(&target));
+        assert!(!tree.find(&target));
+        assert!(!tree.delete(&target));
+
+        // Removing an inner node keeps its descendants in the tree
+        assert_eq!(size(tree.root.clone()), 7);
+        assert!(tree.delete(&String::from("Hello")));
+        assert_eq!(size(tree.root.clone()), 6);
+        assert!(tree.find(&155));
+        assert!(tree.find(&7));
+
+        assert!(tree.delete(&1));
+        assert!(tree.find(&155));
+        assert_eq!(size(tree.root.clone()), 5);
+    }
+
+    #[test]
+    fn test_subtree_sums() {
+        let root = sample_tree();
+        assert_eq!(subtree_sum(Some(root.clone())), None);
+
+        annotate_subtree_sums(Some(root.clone()));
+        let expected = 1.0 + 155.0 + 200.0 + 3.65f32 as f64;
+        assert_eq!(subtree_sum(Some(root.clone())), Some(expected));
+
+        let left = root.borrow().left.clone();
+        assert_eq!(subtree_sum(left), Some(155.0));
+    }
+
+    #[test]
+    fn test_outline() {
+        let outline = to_outline(Some(sample_tree()));
+        assert_eq!(
+            outline,
+            "1\n  \"Hello\"\n    \"Howdy\"\n    155\n  3.65\n    200\n    \"Hi pal !\"\n"
+        );
+        assert_eq!(to_outline(from_outline(&outline)), outline);
+
+        let edited = outline.replace("    \"Howdy\"\n", "    \"Howdy\"\n      ~\n      42\n");
+        let root = from_outline(&edited).unwrap();
+        let howdy = root.borrow().left.clone().unwrap().borrow().left.clone().unwrap();
+        assert!(howdy.borrow().left.is_none());
+        assert!(howdy.borrow().right.as_ref().unwrap().borrow().value.equals(&42));
+        assert_eq!(to_outline(Some(root)), edited);
+
+        assert!(from_outline("1\n   2\n").is_none());
+    }
+
+    #[test]
+    fn test_diagonal_groups() {
+        let groups: Vec<Vec<String>> = diagonal_groups(Some(sample_tree()))
+            .iter()
+            .map(|group| group.iter().map(|n| outline_value(n.borrow().value.as_ref())).collect())
+            .collect();
+        assert_eq!(
+            groups,
+            vec![
+                vec!["1", "3.65", "\"Hi pal !\""],
+                vec!["\"Hello\"", "155", "200"],
+                vec!["\"Howdy\""],
+            ]
+        );
+        assert!(diagonal_groups(None).is_empty());
+    }
+
+    #[test]
+    fn test_balance_factors() {
+        let factors = balance_factors(build_tree_from_list(&[1, 2, 3, 4, 5, 6, 7]));
+        assert_eq!(factors.len(), 7);
+        assert!(factors.iter().all(|(_, f)| *f == 0));
+
+        let root = new_node(1);
+        let mid = new_node(2);
+        mid.borrow_mut().add_right(new_node(3));
+        root.borrow_mut().add_right(mid);
+        let factors: Vec<i32> = balance_factors(Some(root.clone())).iter().map(|(_, f)| *f).collect();
+        assert_eq!(factors, vec![0, -1, -2]);
+        assert!(Rc::ptr_eq(&balance_factors(Some(root.clone()))[2].0, &root));
+        assert!(balance_factors(None).is_empty());
+    }
+
+    #[test]
+    fn test_level_order() {
+        let levels = level_order(Some(sample_tree()));
+        let lengths: Vec<usize> = levels.iter().map(|level| level.len()).collect();
+        assert_eq!(lengths, vec![1, 2, 4]);
+        assert!(levels[0][0].equals(&1));
+        assert!(levels[1][0].equals(&String::from("Hello")));
+        assert!(levels[1][1].equals(&3.65f32));
+        let last: Vec<String> = levels[2].iter().map(|v| outline_value(v.as_ref())).collect();
+        assert_eq!(last, vec!["\"Howdy\"", "155", "200", "\"Hi pal !\""]);
+
+        assert!(level_order(None).is_empty());
+    }
+
+    #[test]
+    fn test_pre_and_postorder() {
+        let mut visited = Vec::new();
+        preorder_with(Some(sample_tree()), &mut |value| visited.push(outline_value(value)));
+        assert_eq!(visited, vec!["1", "\"Hello\"", "\"Howdy\"", "155", "3.65", "200", "\"Hi pal !\""]);
+
+        visited.clear();
+        postorder_with(Some(sample_tree()), &mut |value| visited.push(outline_value(value)));
+        assert_eq!(visited, vec!["\"Howdy\"", "155", "\"Hello\"", "200", "\"Hi pal !\"", "3.65", "1"]);
+
+        visited.clear();
+        preorder_with(None, &mut |value| visited.push(outline_value(value)));
+        assert!(visited.is_empty());
+    }
+
+    #[test]
+    fn test_inorder_iter() {
+        let visited: Vec<String> = InorderIter::new(Some(sample_tree()))
+            .map(|n| outline_value(n.borrow().value.as_ref()))
+            .collect();
+        assert_eq!(visited, vec!["\"Howdy\"", "\"Hello\"", "155", "1", "200", "3.65", "\"Hi pal !\""]);
+
+        let values: Vec<i32> = (1..=20).collect();
+        let sorted: Vec<bool> = InorderIter::new(build_tree_from_list(&values))
+            .zip(&values)
+            .map(|(n, v)| n.borrow().value.equals(v))
+            .collect();
+        assert_eq!(sorted, vec![true; 20]);
+        assert_eq!(InorderIter::new(None).count(), 0);
+    }
+
+    #[test]
+    fn test_size() {
+        assert_eq!(size(Some(sample_tree())), 7);
+        assert_eq!(size(build_tree_from_list(&[1, 2, 3, 4, 5])), 5);
+        assert_eq!(size(None), 0);
+    }
+
+    #[test]
+    fn test_to_dot() {
+        let dot = to_dot(Some(sample_tree()));
+        assert!(dot.starts_with("digraph tree {\n"));
+        assert!(dot.ends_with("}\n"));
+        assert!(dot.contains("    n0 [label=\"i32 value: 1\"];\n"));
+        assert!(dot.contains("    n1 [label=\"String value: Hello\"];\n"));
+        assert!(dot.contains("    n0 -> n1 [label=\"left\"];\n"));
+        assert!(dot.contains("    n0 -> n4 [label=\"right\"];\n"));
+        assert!(dot.contains("    n4 [label=\"Float value: 3.65\"];\n"));
+        assert_eq!(dot.matches(" -> ").count(), 6);
+
+        assert_eq!(to_dot(None), "digraph tree {\n}\n");
+
+        let root = new_node(String::from("C:\\dir\\"));
+        root.borrow_mut().add_left(new_node(String::from("say \"hi\"")));
+        let dot = to_dot(Some(root));
+        assert!(dot.contains("    n0 [label=\"String value: C:\\\\dir\\\\\"];\n"));
+        assert!(dot.contains("    n1 [label=\"String value: say \\\"hi\\\"\"];\n"));
+    }
+
+    #[test]
+    fn test_label() {
+        assert_eq!(42.label(), "i32 value: 42");
+        assert_eq!(String::from("Hello").label(), "String value: Hello");
+        assert_eq!(3.65f32.label(), "Float value: 3.65");
+        let value: Box<dyn Value> = Box::new(7);
+        assert_eq!(value.label(), "i32 value: 7");
+        assert_eq!(format!("{:?}", value), "i32 value: 7");
+    }
+
+    #[test]
+    fn test_lowest_common_ancestor() {
+        let root = sample_tree();
+        let lca = |a: &dyn Value, b: &dyn Value| {
+            lowest_common_ancestor(Some(root.clone()), a, b).map(|n| n.borrow().value.label())
+        };
+        assert_eq!(lca(&String::from("Howdy"), &155), Some(String::from("String value: Hello")));
+        assert_eq!(lca(&155, &200), Some(String::from("i32 value: 1")));
+        assert_eq!(lca(&String::from("Hello"), &155), Some(String::from("String value: Hello")));
+        assert_eq!(lca(&200, &200), Some(String::from("i32 value: 200")));
+        assert_eq!(lca(&155, &999), None);
+        assert!(lowest_common_ancestor(None, &1, &1).is_none());
+    }
+
+    #[test]
+    fn test_deep_clone() {
+        let original = sample_tree();
+        let copy = deep_clone(Some(original.clone())).unwrap();
+        assert!(!Rc::ptr_eq(&original, &copy));
+        assert_eq!(to_outline(Some(copy.clone())), to_outline(Some(original.clone())));
+
+        copy.borrow_mut().value = Box::new(99);
+        copy.borrow().left.clone().unwrap().borrow_mut().right = None;
+        assert!(original.borrow().value.equals(&1));
+        assert!(find_value(Some(original.clone()), &155));
+        assert!(!find_value(Some(copy), &155));
+        assert!(deep_clone(None).is_none());
+    }
+
+    #[test]
+    fn test_trees_equal() {
+        let original = sample_tree();
+        let copy = deep_clone(Some(original.clone()));
+        assert!(trees_equal(Some(original.clone()), copy.clone()));
+        assert!(trees_equal(None, None));
+        assert!(!trees_equal(Some(original.clone()), None));
+
+        let copy = copy.unwrap();
+        copy.borrow().right.clone().unwrap().borrow_mut().left = Some(new_node(200.0f32));
+        assert!(!trees_equal(Some(original.clone()), Some(copy.clone())));
+
+        let copy = deep_clone(Some(original.clone())).unwrap();
+        copy.borrow().right.clone().unwrap().borrow_mut().right = None;
+        assert!(!trees_equal(Some(original), Some(copy)));
+    }
+
+    #[test]
+    fn test_find_path() {
+        let root = sample_tree();
+        let labels = |path: Vec<Rc<RefCell<Node>>>| -> Vec<String> {
+            path.iter().map(|n| outline_value(n.borrow().value.as_ref())).collect()
+        };
+        assert_eq!(labels(find_path(Some(root.clone()), &155).unwrap()), vec!["1", "\"Hello\"", "155"]);
+        assert_eq!(labels(find_path(Some(root.clone()), &1).unwrap()), vec!["1"]);
+        assert!(find_path(Some(root.clone()), &999).is_none());
+        assert!(find_path(None, &1).is_none());
+
+        root.borrow().left.clone().unwrap().borrow().left.clone().unwrap().borrow_mut().add_left(new_node(200));
+        assert_eq!(labels(find_path(Some(root), &200).unwrap()), vec!["1", "\"Hello\"", "\"Howdy\"", "200"]);
+    }
+
+    #[test]
+    fn test_mirror() {
+        let root = sample_tree();
+        let inorder = |root: &Rc<RefCell<Node>>| -> Vec<String> {
+            InorderIter::new(Some(root.clone())).map(|n| outline_value(n.borrow().value.as_ref())).collect()
+        };
+        let before = inorder(&root);
+        mirror(Some(root.clone()));
+        let mut after = inorder(&root);
+        after.reverse();
+        assert_eq!(after, before);
+        assert!(root.borrow().left.clone().unwrap().borrow().value.equals(&3.65f32));
+
+        mirror(Some(root.clone()));
+        assert!(trees_equal(Some(root), Some(sample_tree())));
+        mirror(None);
+    }
+
+    #[test]
+    fn test_diameter() {
+        assert_eq!(diameter(Some(sample_tree())), 5);
+        assert_eq!(diameter(None), 0);
+        assert_eq!(diameter(Some(new_node(1))), 1);
+
+        // Long arms hang off the left child, so the longest path skips the root
+        let root = new_node(0);
+        let left = new_node(1);
+        root.borrow_mut().set_children(Some(left.clone()), Some(new_node(2)));
+        let (mut outer, mut inner) = (left.clone(), left.clone());
+        for i in 0..3 {
+            let next = new_node(10 + i);
+            outer.borrow_mut().add_left(next.clone());
+            outer = next;
+            let next = new_node(20 + i);
+            inner.borrow_mut().add_right(next.clone());
+            inner = next;
+        }
+        assert_eq!(height(Some(root.clone())), 5);
+        assert_eq!(diameter(Some(root)), 7);
+    }
+
+    #[test]
+    fn test_is_balanced() {
+        let values: Vec<i32> = (1..=20).collect();
+        assert!(is_balanced(build_tree_from_list(&values)));
+        assert!(is_balanced(Some(sample_tree())));
+        assert!(is_balanced(None));
+
+        let root = new_node(1);
+        let middle = new_node(2);
+        middle.borrow_mut().add_right(new_node(3));
+        root.borrow_mut().add_right(middle.clone());
+        assert!(!is_balanced(Some(root)));
+        assert!(is_balanced(Some(middle)));
+    }
+
+    #[test]
+    fn test_serialize_tree() {
+        let root = sample_tree();
+        root.borrow().left.clone().unwrap().borrow().left.clone().unwrap().borrow_mut().add_right(new_node(-7));
+        let bytes = serialize_tree(Some(root.clone()));
+        assert_eq!(&bytes[..5], &[TAG_I32, 1, 0, 0, 0]);
+
+        let mut data = bytes.as_slice();
+        let decoded = deserialize_tree(&mut data);
+        assert!(data.is_empty());
+        assert!(trees_equal(decoded, Some(root)));
+
+        assert_eq!(serialize_tree(None), vec![NO_NODE]);
+        assert!(deserialize_tree(&mut [NO_NODE].as_slice()).is_none());
+        assert!(deserialize_tree(&mut &bytes[..bytes.len() - 1]).is_none());
+        assert!(deserialize_tree(&mut [7u8].as_slice()).is_none());
+
+        // A long run of i32 tags, each node the left child of the previous one
+        let hostile: Vec<u8> = std::iter::repeat_n([TAG_I32, 0, 0, 0, 0], 400_000).flatten().collect();
+        assert!(deserialize_tree(&mut hostile.as_slice()).is_none());
+
+        let deepest = new_node(0);
+        let mut last = deepest.clone();
+        for value in 1..MAX_DESERIALIZE_DEPTH as i32 {
+            let next = new_node(value);
+            last.borrow_mut().add_left(next.clone());
+            last = next;
+        }
+        let bytes = serialize_tree(Some(deepest.clone()));
+        assert!(trees_equal(deserialize_tree(&mut bytes.as_slice()), Some(deepest)));
+    }
+
+    #[test]
+    fn test_delete() {
+        let preorder = |root: Option<Rc<RefCell<Node>>>| {
+            let mut visited = Vec::new();
+            preorder_with(root, &mut |value| visited.push(outline_value(value)));
+            visited
+        };
+
+        let root = delete(Some(sample_tree()), &155);
+        assert_eq!(size(root.clone()), 6);
+        assert_eq!(preorder(root.clone()), vec!["1", "\"Hello\"", "\"Howdy\"", "3.65", "200", "\"Hi pal !\""]);
+
+        let root = delete(root, &String::from("Hello"));
+        assert_eq!(preorder(root.clone()), vec!["1", "\"Howdy\"", "3.65", "200", "\"Hi pal !\""]);
+
+        // Two children: 200, the leftmost node of the right subtree, takes the root's place
+        let removed = root.clone().unwrap();
+        let root = delete(root, &1);
+        assert_eq!(preorder(root.clone()), vec!["200", "\"Howdy\"", "3.65", "\"Hi pal !\""]);
+        assert!(removed.borrow().left.is_none() && removed.borrow().right.is_none());
+
+        let values: Vec<i32> = (1..=7).collect();
+        let root = delete(build_tree_from_list(&values), &4);
+        let inorder: Vec<String> = InorderIter::new(root.clone()).map(|n| outline_value(n.borrow().value.as_ref())).collect();
+        assert_eq!(inorder, vec!["1", "2", "3", "5", "6", "7"]);
+        assert!(root.unwrap().borrow().value.equals(&5));
+
+        assert_eq!(size(delete(Some(sample_tree()), &999)), 7);
+        assert!(delete(Some(new_node(1)), &1).is_none());
+    }
+
+    #[test]
+    fn test_has_cycle() {
+        let root = sample_tree();
+        assert!(!has_cycle(Some(root.clone())));
+        assert!(!has_cycle(None));
+
+        // The same subtree on both sides is shared, not a cycle
+        let shared = new_node(5);
+        let diamond = new_node(0);
+        diamond.borrow_mut().set_children(Some(shared.clone()), Some(shared));
+        assert!(!has_cycle(Some(diamond)));
+
+        let leaf = root.borrow().right.clone().unwrap().borrow().right.clone().unwrap();
+        leaf.borrow_mut().add_left(root.clone());
+        assert!(has_cycle(Some(root.clone())));
+        assert!(!print_tree_checked(root.clone()));
+        leaf.borrow_mut().left = None;
+
+        root.borrow_mut().add_left(root.clone());
+        assert!(has_cycle(Some(root.clone())));
+        root.borrow_mut().left = None;
+        assert!(print_tree_checked(root));
+    }
+
+    #[test]
+    fn test_to_sorted_vec() {
+        // Right-leaning chain 1 -> 2 -> ... -> 15
+        let root = new_node(1);
+        let mut last = root.clone();
+        for value in 2..=15 {
+            let next = new_node(value);
+            last.borrow_mut().add_right(next.clone());
+            last = next;
+        }
+        assert_eq!(height(Some(root.clone())), 15);
+
+        let values = to_sorted_vec::<i32>(Some(root));
+        assert_eq!(values, (1..=15).collect::<Vec<i32>>());
+        let rebuilt = build_tree_from_list(&values);
+        assert_eq!(height(rebuilt.clone()), 4);
+        assert!(is_balanced(rebuilt));
+
+        assert_eq!(to_sorted_vec::<i32>(Some(sample_tree())), vec![155, 1, 200]);
+        assert!(to_sorted_vec::<f32>(None).is_empty());
+    }
+
+    #[test]
+    fn test_outline_escaping() {
+        let tricky = String::from("two\nlines, a \"quote\" and C:\\dir\\");
+        let root = new_node(tricky.clone());
+        root.borrow_mut().set_children(Some(new_node(String::from("\\n"))), Some(new_node(String::from("\""))));
+
+        let outline = to_outline(Some(root.clone()));
+        assert_eq!(outline.lines().count(), 3);
+        assert!(outline.starts_with("\"two\\nlines, a \\\"quote\\\" and C:\\\\dir\\\\\"\n"));
+        let parsed = from_outline(&outline);
+        assert!(trees_equal(parsed.clone(), Some(root)));
+        assert!(parsed.unwrap().borrow().value.equals(&tricky));
+
+        assert!(from_outline("\"bare \" quote\"").is_none());
+        assert!(from_outline("\"unknown \\t escape\"").is_none());
+        assert!(from_outline("\"dangling \\\"").is_none());
+    }
+}